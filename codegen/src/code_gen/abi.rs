@@ -1,9 +1,13 @@
 use std::{collections::HashMap, io::Write};
 
+use anyhow::anyhow;
+
 use crate::definitions::Definition;
 
 mod type_info;
 pub use type_info::*;
+mod type_name;
+pub use type_name::*;
 
 pub trait CodeGenProvider<C: CodeGenContext> {
     fn get_pre_processor(&self) -> Box<dyn PreProcessor<C>>;
@@ -13,11 +17,28 @@ pub trait CodeGenProvider<C: CodeGenContext> {
     fn get_object_enum_writer(&self) -> Box<dyn ObjectEnumWriter<C>>;
     fn get_list_writer(&self) -> Box<dyn ListWriter<C>>;
     fn get_map_writer(&self) -> Box<dyn MapWriter<C>>;
+    /// Backends without an equivalent of aliases fail to generate them by default.
+    fn get_alias_writer(&self) -> Box<dyn AliasWriter<C>> {
+        Box::new(UnsupportedWriter)
+    }
+    /// Backends without an equivalent of newtypes fail to generate them by default.
+    fn get_newtype_writer(&self) -> Box<dyn NewtypeWriter<C>> {
+        Box::new(UnsupportedWriter)
+    }
+    /// Whether generic objects can be emitted as is, otherwise every instantiation
+    /// (e.g. `Page<Order>`) is monomorphized into a concrete object before code gen.
+    fn supports_generics(&self) -> bool {
+        false
+    }
 }
 
 pub trait CodeGenContext {
     fn type_dict(&self) -> &HashMap<String, TypeInfo>;
     fn get_writer_for_type(&self, type_info: &TypeInfo) -> anyhow::Result<Box<dyn Write>>;
+    /// Fields the pre-processor boxed to break recursive types, as `package.Type.field`.
+    fn boxed_fields(&self) -> &[String] {
+        &[]
+    }
 }
 
 pub trait PreProcessor<C: CodeGenContext> {
@@ -27,12 +48,7 @@ pub trait PreProcessor<C: CodeGenContext> {
 }
 
 pub trait PackageWriter<C: CodeGenContext> {
-    fn write_package(
-        &self,
-        package: &str,
-        types: &Vec<&TypeInfo>,
-        context: &C,
-    ) -> anyhow::Result<()>;
+    fn write_package(&self, package: &str, types: &[&TypeInfo], context: &C) -> anyhow::Result<()>;
 }
 
 pub trait MapWriter<C: CodeGenContext> {
@@ -44,6 +60,55 @@ pub trait MapWriter<C: CodeGenContext> {
     ) -> anyhow::Result<()>;
 }
 
+pub trait AliasWriter<C: CodeGenContext> {
+    fn write_alias(
+        &self,
+        writer: &mut dyn Write,
+        type_info: &AliasTypeInfo,
+        context: &C,
+    ) -> anyhow::Result<()>;
+}
+
+pub trait NewtypeWriter<C: CodeGenContext> {
+    fn write_newtype(
+        &self,
+        writer: &mut dyn Write,
+        type_info: &NewtypeTypeInfo,
+        context: &C,
+    ) -> anyhow::Result<()>;
+}
+
+/// Fails to write the types it is given, for backends that do not support them.
+pub struct UnsupportedWriter;
+
+impl<C: CodeGenContext> AliasWriter<C> for UnsupportedWriter {
+    fn write_alias(
+        &self,
+        _writer: &mut dyn Write,
+        type_info: &AliasTypeInfo,
+        _context: &C,
+    ) -> anyhow::Result<()> {
+        Err(anyhow!(
+            "Alias {} is not supported by this backend",
+            type_info.name
+        ))
+    }
+}
+
+impl<C: CodeGenContext> NewtypeWriter<C> for UnsupportedWriter {
+    fn write_newtype(
+        &self,
+        _writer: &mut dyn Write,
+        type_info: &NewtypeTypeInfo,
+        _context: &C,
+    ) -> anyhow::Result<()> {
+        Err(anyhow!(
+            "Newtype {} is not supported by this backend",
+            type_info.name
+        ))
+    }
+}
+
 pub trait ListWriter<C: CodeGenContext> {
     fn write_list(
         &self,
@@ -1,4 +1,9 @@
-use crate::definitions::{Field, FieldConfig, SimpleType, TypeConfig};
+use std::str::FromStr;
+
+use fluorite::Any;
+
+use super::TypeName;
+use crate::definitions::{EnumRepresentation, Field, FieldConfig, NamingConvention, TypeConfig};
 
 pub enum TypeInfo {
     Object(ObjectTypeInfo),
@@ -6,33 +11,8 @@ pub enum TypeInfo {
     ObjectEnum(ObjectEnumTypeInfo),
     List(ListTypeInfo),
     Map(MapTypeInfo),
-}
-
-pub enum TypeName {
-    Any,
-    Simple(SimpleType),
-    CustomType(String),
-}
-
-impl TypeName {
-    pub fn is_custom_type(&self) -> bool {
-        match self {
-            TypeName::Simple(_) => false,
-            TypeName::CustomType(_) => true,
-            TypeName::Any => false,
-        }
-    }
-
-    pub fn from_str(field_type: &str) -> TypeName {
-        let opt_simple_type = SimpleType::all_values()
-            .into_iter()
-            .find(|t| t.to_string() == field_type);
-        match opt_simple_type {
-            Some(t) => TypeName::Simple(t),
-            None if field_type == "Any" => TypeName::Any,
-            None => TypeName::CustomType(field_type.to_owned()),
-        }
-    }
+    Alias(AliasTypeInfo),
+    Newtype(NewtypeTypeInfo),
 }
 
 pub struct ListTypeInfo {
@@ -48,47 +28,103 @@ pub struct MapTypeInfo {
     pub value_type: TypeName,
 }
 
+/// Another name for a type, the same type as its target in every backend.
+pub struct AliasTypeInfo {
+    pub package: String,
+    pub name: String,
+    pub target_type: TypeName,
+}
+
+/// A distinct type wrapping its target type, while keeping the target's wire format.
+pub struct NewtypeTypeInfo {
+    pub package: String,
+    pub name: String,
+    pub target_type: TypeName,
+}
+
 pub struct ObjectEnumTypeInfo {
     pub package: String,
     pub name: String,
-    pub type_tag: String,
+    pub tagging: ObjectEnumTagging,
     pub values: Vec<ObjectEnumValue>,
     pub configs: Option<TypeConfig>,
+    /// The naming convention of variant names on the wire.
+    pub rename_all: Option<NamingConvention>,
+}
+
+/// How the variant of an object enum is told apart on the wire.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObjectEnumTagging {
+    /// The tag is a field next to the fields of the variant, e.g. `{"type": "A", "a": 1}`, while
+    /// payloads other than objects are nested, e.g. `{"type": "B", "content": [1, 2]}`.
+    Internal { tag: String, content: String },
+    /// The tag and the variant are siblings, e.g. `{"type": "A", "content": {"a": 1}}`.
+    Adjacent { tag: String, content: String },
+    /// The variant is keyed by its tag, e.g. `{"A": {"a": 1}}`.
+    External,
+    /// No tag at all, the first variant matching the payload wins, e.g. `{"a": 1}`.
+    Untagged,
 }
 
 pub enum ObjectEnumValue {
     Simple(String),
     CustomType(String),
+    /// A named variant carrying a payload of any type, e.g. `Tags(List<String>)`.
+    Wrapped(String, TypeName),
 }
 
 pub struct EnumTypeInfo {
     pub package: String,
     pub name: String,
     pub values: Vec<String>,
+    /// The naming convention of values on the wire.
+    pub rename_all: Option<NamingConvention>,
+    /// Whether values are names or integers on the wire.
+    pub representation: EnumRepresentation,
+    /// The discriminant of every value, for integer enums or enums with explicit discriminants.
+    pub discriminants: Option<Vec<i32>>,
+    /// Whether values unknown to this version are kept instead of failing deserialization.
+    pub allow_unknown: bool,
+    pub configs: Option<TypeConfig>,
 }
 
+#[derive(Clone)]
 pub struct ObjectTypeInfo {
     pub package: String,
     pub name: String,
+    pub type_params: Vec<String>,
     pub fields: Vec<ObjectField>,
     pub is_object_enum_value: bool,
+    /// Whether the object is a type of its own, which is not the case for objects only used as
+    /// inline variants of object enums.
+    pub is_standalone: bool,
+    /// The naming convention of field names on the wire.
+    pub rename_all: Option<NamingConvention>,
+    /// The field identifying an object, whose type is the target of `Ref<Object>`s.
+    pub key_field: Option<String>,
+    pub configs: Option<TypeConfig>,
 }
 
+#[derive(Clone)]
 pub struct ObjectField {
     pub name: String,
     pub field_type: TypeName,
     pub config: Option<FieldConfig>,
     pub optional: Option<bool>,
+    pub default: Option<Any>,
 }
 
-impl From<&Field> for ObjectField {
-    fn from(f: &Field) -> Self {
-        ObjectField {
+impl TryFrom<&Field> for ObjectField {
+    type Error = anyhow::Error;
+
+    fn try_from(f: &Field) -> anyhow::Result<Self> {
+        Ok(ObjectField {
             name: f.name.clone(),
-            field_type: TypeName::from_str(f.field_type.as_str()),
+            field_type: TypeName::from_str(f.field_type.as_str())?,
             config: f.configs.clone(),
             optional: f.optional,
-        }
+            default: f.default.clone(),
+        })
     }
 }
 
@@ -98,6 +134,23 @@ impl ObjectField {
     }
 }
 
+impl ObjectEnumTypeInfo {
+    /// The tag of a variant on the wire, which is either set explicitly in `tags` or derived
+    /// from its name, e.g. `post_code` for `PostCode` with `rename_all: snake_case`.
+    pub fn variant_tag(&self, variant: &str) -> String {
+        let tag = self
+            .configs
+            .as_ref()
+            .and_then(|c| c.tags.as_ref())
+            .and_then(|tags| tags.get(variant));
+        match (tag, &self.rename_all) {
+            (Some(tag), _) => tag.clone(),
+            (None, Some(convention)) => convention.apply(variant),
+            (None, None) => variant.to_owned(),
+        }
+    }
+}
+
 impl TypeInfo {
     pub fn is_object_enum_value(&self) -> bool {
         match self {
@@ -106,6 +159,14 @@ impl TypeInfo {
         }
     }
 
+    /// Whether code is generated for the type on its own.
+    pub fn is_standalone(&self) -> bool {
+        match self {
+            TypeInfo::Object(o) => o.is_standalone,
+            _ => true,
+        }
+    }
+
     pub fn get_referrenced_types(&self) -> Vec<String> {
         match &self {
             TypeInfo::Object(object) => {
@@ -116,28 +177,21 @@ impl TypeInfo {
             TypeInfo::ObjectEnum(e) => e
                 .values
                 .iter()
-                .filter_map(|v| match v {
-                    ObjectEnumValue::Simple(_) => None,
-                    ObjectEnumValue::CustomType(t) => Some(t.clone()),
+                .flat_map(|v| match v {
+                    ObjectEnumValue::Simple(_) => vec![],
+                    ObjectEnumValue::CustomType(t) => vec![t.clone()],
+                    ObjectEnumValue::Wrapped(_, t) => t.custom_types(),
                 })
                 .collect(),
-            TypeInfo::List(l) => match &l.item_type {
-                TypeName::Simple(_) => vec![],
-                TypeName::CustomType(name) => vec![name.to_owned()],
-                TypeName::Any => vec![],
-            },
+            TypeInfo::List(l) => l.item_type.custom_types(),
             TypeInfo::Map(m) => Self::get_custom_types(vec![&m.key_type, &m.value_type]),
+            TypeInfo::Alias(a) => a.target_type.custom_types(),
+            TypeInfo::Newtype(n) => n.target_type.custom_types(),
         }
     }
 
     fn get_custom_types(types: Vec<&TypeName>) -> Vec<String> {
-        types
-            .into_iter()
-            .filter_map(|t| match t {
-                TypeName::CustomType(name) => Some(name.clone()),
-                _ => None,
-            })
-            .collect()
+        types.into_iter().flat_map(TypeName::custom_types).collect()
     }
 
     pub fn type_name(&self) -> &str {
@@ -147,6 +201,8 @@ impl TypeInfo {
             TypeInfo::ObjectEnum(o) => o.name.as_str(),
             TypeInfo::List(l) => l.name.as_str(),
             TypeInfo::Map(m) => m.name.as_str(),
+            TypeInfo::Alias(a) => a.name.as_str(),
+            TypeInfo::Newtype(n) => n.name.as_str(),
         }
     }
 
@@ -157,6 +213,8 @@ impl TypeInfo {
             TypeInfo::ObjectEnum(o) => o.package.as_str(),
             TypeInfo::List(l) => l.package.as_str(),
             TypeInfo::Map(m) => m.package.as_str(),
+            TypeInfo::Alias(a) => a.package.as_str(),
+            TypeInfo::Newtype(n) => n.package.as_str(),
         }
     }
 }
@@ -0,0 +1,302 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use anyhow::anyhow;
+
+use crate::definitions::SimpleType;
+
+/// A resolved type expression, e.g. `String`, `Page<Order>` or `Map<String, List<Order>>`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeName {
+    Any,
+    Simple(SimpleType),
+    CustomType(String),
+    List(Box<TypeName>),
+    Map(Box<TypeName>, Box<TypeName>),
+    Set(Box<TypeName>),
+    Optional(Box<TypeName>),
+    /// An instantiation of a generic object, e.g. `Page<Order>`.
+    Generic(String, Vec<TypeName>),
+    /// A type parameter of the generic object it appears in, e.g. `T` in `Page<T>`.
+    TypeParam(String),
+    /// The key of an object, e.g. `Ref<User>`, replaced by the key type of the object once all
+    /// types are known.
+    Ref(Box<TypeName>),
+}
+
+impl TypeName {
+    pub fn is_custom_type(&self) -> bool {
+        matches!(self, TypeName::CustomType(_))
+    }
+
+    /// All custom types referenced by this type expression, including nested ones.
+    pub fn custom_types(&self) -> Vec<String> {
+        match self {
+            TypeName::Any | TypeName::Simple(_) | TypeName::TypeParam(_) => vec![],
+            TypeName::CustomType(name) => vec![name.clone()],
+            TypeName::Generic(name, args) => {
+                let mut types = vec![name.clone()];
+                types.extend(args.iter().flat_map(TypeName::custom_types));
+                types
+            }
+            TypeName::List(item)
+            | TypeName::Set(item)
+            | TypeName::Optional(item)
+            | TypeName::Ref(item) => item.custom_types(),
+            TypeName::Map(key, value) => {
+                let mut types = key.custom_types();
+                types.extend(value.custom_types());
+                types
+            }
+        }
+    }
+
+    /// All type parameters used by this type expression.
+    pub fn type_params(&self) -> Vec<String> {
+        let mut params = Vec::new();
+        self.map_leaves(&mut |t| {
+            if let TypeName::TypeParam(p) = t {
+                if !params.contains(p) {
+                    params.push(p.clone());
+                }
+            }
+            None
+        });
+        params
+    }
+
+    /// Turns references to any of `params` into [`TypeName::TypeParam`]s.
+    pub fn with_type_params(&self, params: &[String]) -> TypeName {
+        self.map_leaves(&mut |t| match t {
+            TypeName::CustomType(name) if params.contains(name) => {
+                Some(TypeName::TypeParam(name.clone()))
+            }
+            _ => None,
+        })
+    }
+
+    /// Replaces type parameters with the types bound to them.
+    pub fn substitute(&self, bindings: &HashMap<String, TypeName>) -> TypeName {
+        self.map_leaves(&mut |t| match t {
+            TypeName::TypeParam(p) => bindings.get(p).cloned(),
+            _ => None,
+        })
+    }
+
+    /// Rebuilds this type expression, replacing every leaf for which `f` returns a new type.
+    /// The name of a generic type is passed to `f` as a [`TypeName::CustomType`] leaf and
+    /// renamed if `f` returns another custom type. A [`TypeName::Ref`] is passed to `f` as a
+    /// whole before its target.
+    pub fn map_leaves(&self, f: &mut impl FnMut(&TypeName) -> Option<TypeName>) -> TypeName {
+        match self {
+            TypeName::List(item) => TypeName::List(Box::new(item.map_leaves(f))),
+            TypeName::Set(item) => TypeName::Set(Box::new(item.map_leaves(f))),
+            TypeName::Optional(item) => TypeName::Optional(Box::new(item.map_leaves(f))),
+            TypeName::Map(key, value) => {
+                TypeName::Map(Box::new(key.map_leaves(f)), Box::new(value.map_leaves(f)))
+            }
+            TypeName::Generic(name, args) => {
+                let name = match f(&TypeName::CustomType(name.clone())) {
+                    Some(TypeName::CustomType(renamed)) => renamed,
+                    _ => name.clone(),
+                };
+                TypeName::Generic(name, args.iter().map(|a| a.map_leaves(f)).collect())
+            }
+            TypeName::Ref(target) => {
+                f(self).unwrap_or_else(|| TypeName::Ref(Box::new(target.map_leaves(f))))
+            }
+            leaf => f(leaf).unwrap_or_else(|| leaf.clone()),
+        }
+    }
+}
+
+impl Display for TypeName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeName::Any => write!(f, "Any"),
+            TypeName::Simple(t) => write!(f, "{}", t),
+            TypeName::CustomType(name) => write!(f, "{}", name),
+            TypeName::List(item) => write!(f, "List<{}>", item),
+            TypeName::Map(key, value) => write!(f, "Map<{}, {}>", key, value),
+            TypeName::Set(item) => write!(f, "Set<{}>", item),
+            TypeName::Optional(item) => write!(f, "Optional<{}>", item),
+            TypeName::Generic(name, args) => {
+                let args = args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+                write!(f, "{}<{}>", name, args.join(", "))
+            }
+            TypeName::TypeParam(name) => write!(f, "{}", name),
+            TypeName::Ref(target) => write!(f, "Ref<{}>", target),
+        }
+    }
+}
+
+impl FromStr for TypeName {
+    type Err = anyhow::Error;
+
+    /// Parses a type expression: `Name` or `Name<Arg, ...>` where arguments are type
+    /// expressions themselves.
+    fn from_str(field_type: &str) -> anyhow::Result<TypeName> {
+        let mut parser = TypeExprParser {
+            input: field_type,
+            pos: 0,
+        };
+        let type_name = parser.parse_type()?;
+        parser.skip_whitespaces();
+        match parser.pos == field_type.len() {
+            true => Ok(type_name),
+            _ => Err(parser.error("unexpected trailing characters")),
+        }
+    }
+}
+
+struct TypeExprParser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> TypeExprParser<'a> {
+    fn parse_type(&mut self) -> anyhow::Result<TypeName> {
+        let name = self.parse_identifier()?;
+        let mut args = Vec::new();
+        if self.consume('<') {
+            loop {
+                args.push(self.parse_type()?);
+                if self.consume(',') {
+                    continue;
+                }
+                if self.consume('>') {
+                    break;
+                }
+                return Err(self.error("expected ',' or '>'"));
+            }
+        }
+        self.resolve(name, args)
+    }
+
+    fn resolve(&self, name: &str, mut args: Vec<TypeName>) -> anyhow::Result<TypeName> {
+        let expected_args = match name {
+            "List" | "Set" | "Optional" | "Ref" => 1,
+            "Map" => 2,
+            "Any" => 0,
+            _ if SimpleType::from(name).is_some() => 0,
+            _ if !args.is_empty() => return Ok(TypeName::Generic(name.to_owned(), args)),
+            _ => 0,
+        };
+        if args.len() != expected_args {
+            return Err(anyhow!(
+                "Invalid type expression `{}`: {} expects {} type argument(s) but got {}",
+                self.input,
+                name,
+                expected_args,
+                args.len()
+            ));
+        }
+        let type_name = match name {
+            "List" => TypeName::List(Box::new(args.remove(0))),
+            "Set" => TypeName::Set(Box::new(args.remove(0))),
+            "Optional" => TypeName::Optional(Box::new(args.remove(0))),
+            "Ref" => TypeName::Ref(Box::new(args.remove(0))),
+            "Map" => {
+                let key = args.remove(0);
+                TypeName::Map(Box::new(key), Box::new(args.remove(0)))
+            }
+            "Any" => TypeName::Any,
+            _ => match SimpleType::from(name) {
+                Some(t) => TypeName::Simple(t),
+                None => TypeName::CustomType(name.to_owned()),
+            },
+        };
+        Ok(type_name)
+    }
+
+    fn parse_identifier(&mut self) -> anyhow::Result<&'a str> {
+        self.skip_whitespaces();
+        let start = self.pos;
+        for (i, c) in self.input[start..].char_indices() {
+            let valid = match i {
+                0 => c.is_ascii_alphabetic() || c == '_',
+                _ => c.is_ascii_alphanumeric() || c == '_' || c == '.',
+            };
+            if !valid {
+                break;
+            }
+            self.pos = start + i + c.len_utf8();
+        }
+        match self.pos > start {
+            true => Ok(&self.input[start..self.pos]),
+            _ => Err(self.error("expected a type name")),
+        }
+    }
+
+    fn consume(&mut self, expected: char) -> bool {
+        self.skip_whitespaces();
+        match self.input[self.pos..].starts_with(expected) {
+            true => {
+                self.pos += expected.len_utf8();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn skip_whitespaces(&mut self) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn error(&self, message: &str) -> anyhow::Error {
+        anyhow!(
+            "Invalid type expression `{}`: {} at position {}",
+            self.input,
+            message,
+            self.pos
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::TypeName;
+    use crate::definitions::SimpleType;
+
+    #[test]
+    fn test_parse_type_expressions() -> anyhow::Result<()> {
+        assert_eq!(
+            TypeName::from_str("UInt32")?,
+            TypeName::Simple(SimpleType::UInt32)
+        );
+        assert_eq!(
+            TypeName::from_str("Map<String, List<Optional<Order>>>")?,
+            TypeName::Map(
+                Box::new(TypeName::Simple(SimpleType::String)),
+                Box::new(TypeName::List(Box::new(TypeName::Optional(Box::new(
+                    TypeName::CustomType("Order".to_owned())
+                )))))
+            )
+        );
+        assert_eq!(
+            TypeName::from_str(" Set< protocols.users.User > ")?.to_string(),
+            "Set<protocols.users.User>"
+        );
+        assert!(TypeName::from_str("List<Order").is_err());
+        assert!(TypeName::from_str("Map<String>").is_err());
+        assert_eq!(
+            TypeName::from_str("Page<List<Order>>")?,
+            TypeName::Generic(
+                "Page".to_owned(),
+                vec![TypeName::List(Box::new(TypeName::CustomType(
+                    "Order".to_owned()
+                )))]
+            )
+        );
+        assert!(TypeName::from_str("Page<>").is_err());
+        assert!(TypeName::from_str("String<Order>").is_err());
+        assert!(TypeName::from_str("List<Order>>").is_err());
+        assert_eq!(
+            TypeName::from_str("Ref<User>")?,
+            TypeName::Ref(Box::new(TypeName::CustomType("User".to_owned())))
+        );
+        Ok(())
+    }
+}
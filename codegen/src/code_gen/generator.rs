@@ -4,9 +4,28 @@ use crate::definitions::Definition;
 
 use super::{
     abi::{CodeGenContext, CodeGenProvider, TypeInfo},
-    utils::build_type_dict,
+    source::DefinitionFile,
+    utils::{build_type_dict, monomorphize},
+    validation::validate_definitions,
 };
 
+/// What the generator changed in the definitions to make them compile.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GenerationReport {
+    /// Fields boxed to break recursive types, as `package.Type.field`.
+    pub boxed_fields: Vec<String>,
+}
+
+impl GenerationReport {
+    /// One human readable line per change, e.g. for the CLI or `cargo:warning=`.
+    pub fn notes(&self) -> Vec<String> {
+        self.boxed_fields
+            .iter()
+            .map(|f| format!("boxed {} to break a recursive type", f))
+            .collect()
+    }
+}
+
 pub struct CodeGenerator<C: CodeGenContext> {
     config: Box<dyn CodeGenProvider<C>>,
 }
@@ -16,9 +35,26 @@ impl<C: CodeGenContext> CodeGenerator<C> {
         Self { config }
     }
 
-    pub fn generate(&self, definitions: &Vec<Definition>) -> anyhow::Result<()> {
+    pub fn generate(&self, definitions: &[Definition]) -> anyhow::Result<GenerationReport> {
+        let files = definitions
+            .iter()
+            .cloned()
+            .map(DefinitionFile::from)
+            .collect::<Vec<_>>();
+        self.generate_files(&files)
+    }
+
+    /// Generates code for definitions loaded from files, so that problems found in them can be
+    /// reported with their locations.
+    pub fn generate_files(&self, files: &[DefinitionFile]) -> anyhow::Result<GenerationReport> {
         let pre_processor = self.config.get_pre_processor();
-        let type_dict = build_type_dict(definitions, &pre_processor)?;
+        validate_definitions(files, &|d| pre_processor.get_package_name(d))?;
+        let definitions = files.iter().map(|f| &f.definition).collect::<Vec<_>>();
+        let type_dict = build_type_dict(&definitions, pre_processor.as_ref())?;
+        let type_dict = match self.config.supports_generics() {
+            true => type_dict,
+            _ => monomorphize(type_dict)?,
+        };
         let context = pre_processor.process(type_dict)?;
 
         // group types by packages for next code gen
@@ -33,12 +69,14 @@ impl<C: CodeGenContext> CodeGenerator<C> {
             if let Some(package_writer) = self.config.get_package_writer() {
                 package_writer.write_package(package, &types, &context)?;
             }
-            for type_info in types.into_iter().filter(|t| !t.is_object_enum_value()) {
+            for type_info in types.into_iter().filter(|t| t.is_standalone()) {
                 self.gen_code_for(type_info, &context)?;
             }
         }
 
-        Ok(())
+        Ok(GenerationReport {
+            boxed_fields: context.boxed_fields().to_vec(),
+        })
     }
 
     fn gen_code_for(&self, type_info: &TypeInfo, context: &C) -> anyhow::Result<()> {
@@ -68,6 +106,14 @@ impl<C: CodeGenContext> CodeGenerator<C> {
                 let map_writer = self.config.get_map_writer();
                 map_writer.write_map(&mut writer, map_type_info, context)?;
             }
+            TypeInfo::Alias(alias_type_info) => {
+                let alias_writer = self.config.get_alias_writer();
+                alias_writer.write_alias(&mut writer, alias_type_info, context)?;
+            }
+            TypeInfo::Newtype(newtype_type_info) => {
+                let newtype_writer = self.config.get_newtype_writer();
+                newtype_writer.write_newtype(&mut writer, newtype_type_info, context)?;
+            }
         };
         writer.flush()?;
         Ok(())
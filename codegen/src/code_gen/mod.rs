@@ -1,7 +1,11 @@
 pub mod abi;
 mod generator;
 pub mod rust;
+mod source;
 pub mod ts;
+mod validation;
 
 pub(crate) mod utils;
 pub use generator::*;
+pub use source::*;
+pub use validation::*;
@@ -1,16 +1,24 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fs::{create_dir_all, File, OpenOptions},
     io::{BufWriter, Write},
 };
 
-use crate::code_gen::abi::{CodeGenContext, TypeInfo, TypeName};
+use crate::{
+    code_gen::{
+        abi::{CodeGenContext, ObjectEnumValue, TypeInfo, TypeName},
+        utils::{any_to_f64, any_to_i128},
+    },
+    definitions::{SimpleType, TypeConfig},
+};
+use fluorite::Any;
 
-use super::RustOptions;
+use super::{to_rust_variant_ident, RustOptions};
 use anyhow::anyhow;
 
 pub struct RustContext {
     pub types_dict: HashMap<String, TypeInfo>,
+    pub boxed_fields: Vec<String>,
     pub options: RustOptions,
 }
 
@@ -19,6 +27,10 @@ impl CodeGenContext for RustContext {
         &self.types_dict
     }
 
+    fn boxed_fields(&self) -> &[String] {
+        &self.boxed_fields
+    }
+
     fn get_writer_for_type(&self, type_info: &TypeInfo) -> anyhow::Result<Box<dyn Write>> {
         match self.options.single_file {
             true => self.write_to_mod_file(type_info.package(), true),
@@ -28,8 +40,79 @@ impl CodeGenContext for RustContext {
 }
 
 impl RustContext {
-    pub fn type_descriptions(&self) -> &str {
-        "#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, derive_new::new)]"
+    /// The derives and attributes of a type: the global ones in [`RustOptions`], the serde
+    /// ones, `auto_derives` inferred from the type and the ones in its `configs`. Auto derives
+    /// whose supertraits are not derived, e.g. `Eq` without `PartialEq`, are left out.
+    pub fn type_descriptions(&self, auto_derives: &[&str], configs: Option<&TypeConfig>) -> String {
+        let type_derives = configs
+            .and_then(|c| c.derives.as_ref())
+            .into_iter()
+            .flatten();
+        let configured = self.configured_derives(configs);
+        let mut derives: Vec<&str> = vec![];
+        for derive in self
+            .options
+            .derives
+            .iter()
+            .map(String::as_str)
+            .chain(["serde::Serialize", "serde::Deserialize"])
+            .chain(auto_derives.iter().copied())
+            .chain(type_derives.map(String::as_str))
+        {
+            let supertraits = match derive {
+                "Copy" => &["Clone"][..],
+                "Eq" | "PartialOrd" => &["PartialEq"],
+                "Hash" => &["Eq"],
+                "Ord" => &["Eq", "PartialOrd"],
+                _ => &[],
+            };
+            let is_derivable = configured.contains(&derive)
+                || supertraits
+                    .iter()
+                    .all(|t| configured.contains(t) || derives.contains(t));
+            if is_derivable && !derives.contains(&derive) {
+                derives.push(derive);
+            }
+        }
+        let mut lines = vec![format!("#[derive({})]", derives.join(", "))];
+        let type_attributes = configs
+            .and_then(|c| c.attributes.as_ref())
+            .into_iter()
+            .flatten();
+        for attribute in self.options.attributes.iter().chain(type_attributes) {
+            lines.push(format!("#[{}]", attribute));
+        }
+        lines.join("\n")
+    }
+    /// The global derives in [`RustOptions`] followed by the ones in `configs` of a type.
+    fn configured_derives<'a>(&'a self, configs: Option<&'a TypeConfig>) -> Vec<&'a str> {
+        let type_derives = configs
+            .and_then(|c| c.derives.as_ref())
+            .into_iter()
+            .flatten();
+        self.options
+            .derives
+            .iter()
+            .chain(type_derives)
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Whether a type with `configs` derives `PartialEq`, which `Eq` and `Hash` build on.
+    fn derives_partial_eq(&self, configs: Option<&TypeConfig>) -> bool {
+        self.configured_derives(configs).contains(&"PartialEq")
+    }
+
+    /// `Eq`, and `Hash` as well, when all of `type_names` implement them.
+    pub fn eq_derives(&self, type_names: &[&TypeName]) -> Vec<&'static str> {
+        match (
+            type_names.iter().all(|t| self.is_hashable(t)),
+            type_names.iter().all(|t| self.is_eq(t)),
+        ) {
+            (true, _) => vec!["Eq", "Hash"],
+            (_, true) => vec!["Eq"],
+            _ => vec![],
+        }
     }
     pub fn write_to_type_file(&self, type_info: &TypeInfo) -> anyhow::Result<Box<dyn Write>> {
         let type_name = type_info.type_name();
@@ -72,15 +155,181 @@ impl RustContext {
     pub fn get_fully_qualified_type_name(&self, type_name: &TypeName) -> anyhow::Result<String> {
         let full_type_name = match type_name {
             TypeName::Simple(t) => self.options.get_simple_type(t),
-            TypeName::CustomType(name) => {
-                let type_info = self
-                    .types_dict
-                    .get(name)
-                    .ok_or_else(|| anyhow!("Cannot find custom type: {}", name))?;
-                self.get_fqn_for_type(type_info)
-            }
+            TypeName::CustomType(name) => self.get_fqn_for_generic_type(name, &[])?,
+            TypeName::Generic(name, args) => self.get_fqn_for_generic_type(name, args)?,
+            TypeName::TypeParam(name) => name.clone(),
+            TypeName::Ref(_) => return Err(anyhow!("Unresolved key reference: {}", type_name)),
             TypeName::Any => self.options.any_type.clone(),
+            TypeName::List(item) => format!("Vec<{}>", self.get_fully_qualified_type_name(item)?),
+            TypeName::Map(key, value) => format!(
+                "std::collections::HashMap<{}, {}>",
+                self.get_fully_qualified_type_name(key)?,
+                self.get_fully_qualified_type_name(value)?
+            ),
+            TypeName::Set(item) => match self.is_hashable(item) {
+                true => format!(
+                    "std::collections::HashSet<{}>",
+                    self.get_fully_qualified_type_name(item)?
+                ),
+                _ => return Err(anyhow!("Set items must be hashable, found: {}", item)),
+            },
+            TypeName::Optional(item) => {
+                format!("Option<{}>", self.get_fully_qualified_type_name(item)?)
+            }
         };
         Ok(full_type_name)
     }
+
+    fn get_fqn_for_generic_type(&self, name: &str, args: &[TypeName]) -> anyhow::Result<String> {
+        let type_info = self
+            .types_dict
+            .get(name)
+            .ok_or_else(|| anyhow!("Cannot find custom type: {}", name))?;
+        let type_params = match type_info {
+            TypeInfo::Object(o) => o.type_params.len(),
+            _ => 0,
+        };
+        if type_params != args.len() {
+            return Err(anyhow!(
+                "{} expects {} type argument(s) but got {}",
+                name,
+                type_params,
+                args.len()
+            ));
+        }
+        let fqn = self.get_fqn_for_type(type_info);
+        match args.is_empty() {
+            true => Ok(fqn),
+            _ => {
+                let args = args
+                    .iter()
+                    .map(|a| self.get_fully_qualified_type_name(a))
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                Ok(format!("{}<{}>", fqn, args.join(", ")))
+            }
+        }
+    }
+
+    /// Whether the generated type implements `Eq` and `Hash`.
+    pub fn is_hashable(&self, type_name: &TypeName) -> bool {
+        self.is_comparable(type_name, true, &mut HashSet::new())
+    }
+
+    /// Whether the generated type implements `Eq`, i.e. no floats or any values are reachable.
+    pub fn is_eq(&self, type_name: &TypeName) -> bool {
+        self.is_comparable(type_name, false, &mut HashSet::new())
+    }
+
+    /// Types already `visited` are assumed to be comparable, so that recursive types are
+    /// decided by their other members.
+    fn is_comparable(
+        &self,
+        type_name: &TypeName,
+        hash: bool,
+        visited: &mut HashSet<String>,
+    ) -> bool {
+        match type_name {
+            TypeName::Simple(
+                SimpleType::String
+                | SimpleType::Bool
+                | SimpleType::UInt32
+                | SimpleType::UInt64
+                | SimpleType::Int32
+                | SimpleType::Int64,
+            ) => true,
+            TypeName::Optional(item) | TypeName::List(item) => {
+                self.is_comparable(item, hash, visited)
+            }
+            // std's hash maps and sets don't implement `Hash`
+            TypeName::Set(item) => !hash && self.is_comparable(item, hash, visited),
+            TypeName::Map(_, value) => !hash && self.is_comparable(value, hash, visited),
+            TypeName::CustomType(name) => {
+                if !visited.insert(name.clone()) {
+                    return true;
+                }
+                match self.types_dict.get(name) {
+                    Some(TypeInfo::Alias(a)) => self.is_comparable(&a.target_type, hash, visited),
+                    Some(TypeInfo::Newtype(n)) => {
+                        self.derives_partial_eq(None)
+                            && self.is_comparable(&n.target_type, hash, visited)
+                    }
+                    Some(TypeInfo::List(l)) => self.is_comparable(&l.item_type, hash, visited),
+                    Some(TypeInfo::Map(m)) => {
+                        !hash && self.is_comparable(&m.value_type, hash, visited)
+                    }
+                    Some(TypeInfo::Enum(e)) => self.derives_partial_eq(e.configs.as_ref()),
+                    // nothing is known about custom Rust types of fields
+                    Some(TypeInfo::Object(o)) => {
+                        self.derives_partial_eq(o.configs.as_ref())
+                            && o.fields.iter().all(|f| {
+                                f.config
+                                    .as_ref()
+                                    .and_then(|c| c.rust_type.as_ref())
+                                    .is_none()
+                                    && self.is_comparable(&f.field_type, hash, visited)
+                            })
+                    }
+                    Some(TypeInfo::ObjectEnum(o)) => {
+                        let allow_unknown = o
+                            .configs
+                            .as_ref()
+                            .and_then(|c| c.allow_unknown)
+                            .unwrap_or(false);
+                        !allow_unknown
+                            && self.derives_partial_eq(o.configs.as_ref())
+                            && o.values.iter().all(|v| match v {
+                                ObjectEnumValue::Simple(_) => true,
+                                ObjectEnumValue::CustomType(name) => self.is_comparable(
+                                    &TypeName::CustomType(name.clone()),
+                                    hash,
+                                    visited,
+                                ),
+                                ObjectEnumValue::Wrapped(_, payload) => {
+                                    self.is_comparable(payload, hash, visited)
+                                }
+                            })
+                    }
+                    None => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Renders a default literal that has already been checked against `type_name`.
+    pub fn get_default_value(&self, type_name: &TypeName, value: &Any) -> anyhow::Result<String> {
+        let literal = match (type_name, value) {
+            (TypeName::Simple(SimpleType::String), Any::String(s)) => format!("{:?}.to_owned()", s),
+            (TypeName::Simple(SimpleType::Bool), Any::Bool(b)) => b.to_string(),
+            (TypeName::Simple(SimpleType::Float32 | SimpleType::Float64), v) => any_to_f64(v)
+                .map(|f| format!("{:?}", f))
+                .ok_or_else(|| anyhow!("Invalid float default value: {:?}", v))?,
+            (TypeName::Simple(_), v) => any_to_i128(v)
+                .map(|i| i.to_string())
+                .ok_or_else(|| anyhow!("Invalid integer default value: {:?}", v))?,
+            (TypeName::CustomType(name), v) => match (self.types_dict.get(name), v) {
+                (Some(TypeInfo::Alias(a)), v) => self.get_default_value(&a.target_type, v)?,
+                (Some(TypeInfo::Newtype(n)), v) => format!(
+                    "{}({})",
+                    self.get_fully_qualified_type_name(type_name)?,
+                    self.get_default_value(&n.target_type, v)?
+                ),
+                (Some(TypeInfo::Enum(_)), Any::String(s)) => {
+                    let type_name = self.get_fully_qualified_type_name(type_name)?;
+                    format!("{}::{}", type_name, to_rust_variant_ident(s))
+                }
+                (Some(TypeInfo::List(_)), Any::List(_)) => "Vec::new()".to_owned(),
+                (Some(TypeInfo::Map(_)), Any::Map(_)) => {
+                    "std::collections::HashMap::new()".to_owned()
+                }
+                (_, v) => return Err(anyhow!("Unsupported default value: {:?}", v)),
+            },
+            (TypeName::List(_), Any::List(_)) => "Vec::new()".to_owned(),
+            (TypeName::Set(_), Any::List(_)) => "std::collections::HashSet::new()".to_owned(),
+            (TypeName::Map(_, _), Any::Map(_)) => "std::collections::HashMap::new()".to_owned(),
+            (TypeName::Optional(item), v) => format!("Some({})", self.get_default_value(item, v)?),
+            (_, v) => return Err(anyhow!("Unsupported default value: {:?}", v)),
+        };
+        Ok(literal)
+    }
 }
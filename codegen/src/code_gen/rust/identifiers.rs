@@ -0,0 +1,149 @@
+use crate::{code_gen::utils::to_pascal_case, definitions::NamingConvention};
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+    "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "try",
+    "type", "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "typeof", "unsized", "virtual", "yield",
+];
+
+// keywords which cannot be used as raw identifiers
+const NON_RAW_KEYWORDS: &[&str] = &["crate", "self", "Self", "super"];
+
+/// Turns a name from a definition file into a valid Rust identifier: keywords become raw
+/// identifiers (`r#type`), characters not allowed in identifiers are replaced with `_` and
+/// a leading digit is prefixed with `_`.
+pub fn to_rust_ident(name: &str) -> String {
+    let mut ident = name
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c,
+            _ => '_',
+        })
+        .collect::<String>();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) || ident == "_" {
+        ident.insert(0, '_');
+    }
+    match ident.as_str() {
+        i if NON_RAW_KEYWORDS.contains(&i) => format!("{}_", ident),
+        i if RUST_KEYWORDS.contains(&i) => format!("r#{}", ident),
+        _ => ident,
+    }
+}
+
+/// Turns a name from a definition file into a Rust enum variant in UpperCamelCase, e.g.
+/// `read-only` becomes `ReadOnly` and `match` becomes `Match`.
+pub fn to_rust_variant_ident(name: &str) -> String {
+    let ident = to_pascal_case(name);
+    match ident.as_str() {
+        "" => "_".to_owned(),
+        "Self" => "Self_".to_owned(),
+        i if i.starts_with(|c: char| c.is_ascii_digit()) => format!("_{}", ident),
+        _ => ident,
+    }
+}
+
+/// The value of `#[serde(rename_all = "...")]` for a naming convention.
+pub fn serde_rename_rule(convention: &NamingConvention) -> &'static str {
+    match convention {
+        NamingConvention::CamelCase => "camelCase",
+        NamingConvention::SnakeCase => "snake_case",
+        NamingConvention::ScreamingSnakeCase => "SCREAMING_SNAKE_CASE",
+        NamingConvention::KebabCase => "kebab-case",
+    }
+}
+
+/// The name serde gives a snake_case field under a `rename_all` rule, following serde's own
+/// conversion, which may differ from [`NamingConvention::apply`] for unusual names.
+pub fn serde_field_name(ident: &str, rule: Option<&NamingConvention>) -> String {
+    let field = serde_name(ident);
+    match rule {
+        None | Some(NamingConvention::SnakeCase) => field.to_owned(),
+        Some(NamingConvention::CamelCase) => {
+            let mut pascal_case = String::new();
+            let mut capitalize = true;
+            for c in field.chars() {
+                match c {
+                    '_' => capitalize = true,
+                    _ if capitalize => {
+                        pascal_case.push(c.to_ascii_uppercase());
+                        capitalize = false;
+                    }
+                    _ => pascal_case.push(c),
+                }
+            }
+            match pascal_case.is_empty() {
+                true => pascal_case,
+                _ => pascal_case[..1].to_ascii_lowercase() + &pascal_case[1..],
+            }
+        }
+        Some(NamingConvention::ScreamingSnakeCase) => field.to_ascii_uppercase(),
+        Some(NamingConvention::KebabCase) => field.replace('_', "-"),
+    }
+}
+
+/// The name serde gives an UpperCamelCase variant under a `rename_all` rule, following serde's
+/// own conversion.
+pub fn serde_variant_name(ident: &str, rule: Option<&NamingConvention>) -> String {
+    let variant = serde_name(ident);
+    let snake_case = || {
+        let mut snake_case = String::new();
+        for (i, c) in variant.char_indices() {
+            if i > 0 && c.is_uppercase() {
+                snake_case.push('_');
+            }
+            snake_case.push(c.to_ascii_lowercase());
+        }
+        snake_case
+    };
+    match rule {
+        None => variant.to_owned(),
+        Some(NamingConvention::CamelCase) => variant[..1].to_ascii_lowercase() + &variant[1..],
+        Some(NamingConvention::SnakeCase) => snake_case(),
+        Some(NamingConvention::ScreamingSnakeCase) => snake_case().to_ascii_uppercase(),
+        Some(NamingConvention::KebabCase) => snake_case().replace('_', "-"),
+    }
+}
+
+/// The name serde uses for an identifier, which differs from the identifier for raw ones.
+pub fn serde_name(ident: &str) -> &str {
+    ident.trim_start_matches("r#")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{serde_field_name, serde_variant_name, to_rust_ident, to_rust_variant_ident};
+    use crate::definitions::NamingConvention;
+
+    #[test]
+    fn test_to_rust_ident() {
+        assert_eq!(to_rust_ident("first_name"), "first_name");
+        assert_eq!(to_rust_ident("type"), "r#type");
+        assert_eq!(to_rust_ident("self"), "self_");
+        assert_eq!(to_rust_ident("2fa-code"), "_2fa_code");
+        assert_eq!(to_rust_ident("read-only"), "read_only");
+        assert_eq!(to_rust_ident("_"), "__");
+    }
+
+    #[test]
+    fn test_to_rust_variant_ident() {
+        assert_eq!(to_rust_variant_ident("Male"), "Male");
+        assert_eq!(to_rust_variant_ident("read-only"), "ReadOnly");
+        assert_eq!(to_rust_variant_ident("match"), "Match");
+        assert_eq!(to_rust_variant_ident("Self"), "Self_");
+        assert_eq!(to_rust_variant_ident("1st"), "_1st");
+        assert_eq!(to_rust_variant_ident("HTTPServer"), "HttpServer");
+    }
+
+    #[test]
+    fn test_serde_names() {
+        let camel_case = Some(&NamingConvention::CamelCase);
+        let kebab_case = Some(&NamingConvention::KebabCase);
+        assert_eq!(serde_field_name("first_name", camel_case), "firstName");
+        assert_eq!(serde_field_name("r#type", camel_case), "type");
+        assert_eq!(serde_field_name("_2fa_code", kebab_case), "-2fa-code");
+        assert_eq!(serde_variant_name("HttpServer", kebab_case), "http-server");
+        assert_eq!(serde_variant_name("ReadOnly", camel_case), "readOnly");
+    }
+}
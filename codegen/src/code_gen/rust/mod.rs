@@ -8,6 +8,8 @@ mod options;
 pub use options::*;
 mod context;
 pub use context::*;
+mod identifiers;
+pub use identifiers::*;
 
 use super::abi::{CodeGenProvider, PackageWriter, PreProcessor};
 
@@ -50,4 +52,16 @@ impl CodeGenProvider<RustContext> for RustProvider {
     fn get_map_writer(&self) -> Box<dyn super::abi::MapWriter<RustContext>> {
         Box::new(RustTypeWriter {})
     }
+
+    fn get_alias_writer(&self) -> Box<dyn super::abi::AliasWriter<RustContext>> {
+        Box::new(RustTypeWriter {})
+    }
+
+    fn get_newtype_writer(&self) -> Box<dyn super::abi::NewtypeWriter<RustContext>> {
+        Box::new(RustTypeWriter {})
+    }
+
+    fn supports_generics(&self) -> bool {
+        true
+    }
 }
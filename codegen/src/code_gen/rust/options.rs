@@ -5,6 +5,10 @@ pub struct RustOptions {
     pub output_dir: String,
     pub single_file: bool,
     pub any_type: String,
+    /// Derives of every generated type, next to the serde ones which are always there.
+    pub derives: Vec<String>,
+    /// Attributes of every generated type, e.g. `serde(deny_unknown_fields)`.
+    pub attributes: Vec<String>,
 }
 
 impl RustOptions {
@@ -13,6 +17,11 @@ impl RustOptions {
             output_dir,
             single_file: true,
             any_type: "fluorite::Any".to_owned(),
+            derives: ["Debug", "Clone", "PartialEq"]
+                .iter()
+                .map(|d| d.to_string())
+                .collect(),
+            attributes: vec![],
         }
     }
 
@@ -26,6 +35,16 @@ impl RustOptions {
         self
     }
 
+    pub fn with_derives(mut self, derives: &[&str]) -> Self {
+        self.derives = derives.iter().map(|d| d.to_string()).collect();
+        self
+    }
+
+    pub fn with_attributes(mut self, attributes: &[&str]) -> Self {
+        self.attributes = attributes.iter().map(|a| a.to_string()).collect();
+        self
+    }
+
     pub fn type_to_file_name(&self, type_name: &str) -> String {
         to_snake_case(type_name)
     }
@@ -1,10 +1,10 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use anyhow::anyhow;
 
 use crate::{
-    code_gen::abi::{PreProcessor, TypeInfo},
-    definitions::Definition,
+    code_gen::abi::{ObjectEnumValue, PreProcessor, TypeInfo, TypeName},
+    definitions::{Definition, FieldConfig, RustTypeWrapper},
 };
 
 use super::{RustContext, RustOptions};
@@ -14,20 +14,252 @@ pub struct RustPreProcessor {
 }
 
 impl PreProcessor<RustContext> for RustPreProcessor {
-    fn process(&self, types_dict: HashMap<String, TypeInfo>) -> anyhow::Result<Box<RustContext>> {
+    fn process(
+        &self,
+        mut types_dict: HashMap<String, TypeInfo>,
+    ) -> anyhow::Result<Box<RustContext>> {
+        let boxed_fields = box_recursive_fields(&mut types_dict)?;
         let context = RustContext {
             types_dict,
+            boxed_fields,
             options: self.options.clone(),
         };
         Ok(Box::new(context))
     }
 
     fn get_package_name(&self, definition: &Definition) -> anyhow::Result<String> {
-        match definition.configs.rust_package.as_ref() {
-            Some(package) => Ok(package.clone()),
-            _ => Err(anyhow!("cannot find package info from definition")),
+        // the legacy `rust_package` is still honored as the entry of the Rust backend
+        let package = definition
+            .configs
+            .as_ref()
+            .and_then(|c| c.rust_package.as_deref())
+            .or(definition.package_for("rust"));
+        match package {
+            Some(package) => Ok(package.to_owned()),
+            _ => Err(anyhow!(
+                "cannot find package info from definition, set `package` or `packages.rust`"
+            )),
         }
     }
 }
 
 impl RustPreProcessor {}
+
+/// Exact searches for the fewest fields to box are limited to recursions with this many
+/// candidates, beyond which a small enough set is good enough.
+const MAX_EXACT_CANDIDATES: usize = 16;
+
+/// An edge of the type graph, whose source holds a value of the target inline.
+struct Edge {
+    source: String,
+    target: String,
+    /// The object field of the edge as `(object, field index)`, if the edge can be boxed.
+    field: Option<(String, usize)>,
+}
+
+/// Boxes the fewest object fields so that no type contains itself, returning the boxed fields.
+/// Lists, sets and maps keep their items on the heap already, as do fields wrapped in `Box`,
+/// `Rc` or `Arc`.
+pub(crate) fn box_recursive_fields(
+    types_dict: &mut HashMap<String, TypeInfo>,
+) -> anyhow::Result<Vec<String>> {
+    let edges = value_edges(types_dict);
+    let mut types = types_dict.keys().cloned().collect::<Vec<_>>();
+    types.sort();
+    let mut visited = HashSet::new();
+    let mut boxed = vec![];
+    for type_name in types.iter() {
+        if visited.contains(type_name) {
+            continue;
+        }
+        let component = strongly_connected(type_name, &edges);
+        visited.extend(component.iter().cloned());
+        let inner = edges
+            .iter()
+            .filter(|e| component.contains(&e.source) && component.contains(&e.target))
+            .collect::<Vec<_>>();
+        if is_acyclic(&inner, &[]) {
+            continue;
+        }
+        let candidates = (0..inner.len())
+            .filter(|i| inner[*i].field.is_some())
+            .collect::<Vec<_>>();
+        if !is_acyclic(&inner, &candidates) {
+            return Err(anyhow!(
+                "{} contains itself through types other than objects, which cannot be boxed",
+                type_name
+            ));
+        }
+        let removed = match candidates.len() <= MAX_EXACT_CANDIDATES {
+            true => fewest_edges(&inner, &candidates),
+            _ => minimal_edges(&inner, &candidates),
+        };
+        boxed.extend(removed.into_iter().filter_map(|i| inner[i].field.clone()));
+    }
+
+    let mut boxed_fields = vec![];
+    for (object, index) in boxed {
+        if let Some(TypeInfo::Object(type_info)) = types_dict.get_mut(&object) {
+            let field = &mut type_info.fields[index];
+            let config = field.config.get_or_insert(FieldConfig {
+                rename: None,
+                rust_type_wrapper: None,
+                rust_item_wrapper: None,
+                rust_type: None,
+                serde_with: None,
+                attributes: None,
+            });
+            config.rust_type_wrapper = Some(RustTypeWrapper::Box);
+            boxed_fields.push(format!("{}.{}", object, field.name));
+        }
+    }
+    boxed_fields.sort();
+    Ok(boxed_fields)
+}
+
+fn value_edges(types_dict: &HashMap<String, TypeInfo>) -> Vec<Edge> {
+    let mut edges = vec![];
+    for (source, type_info) in types_dict.iter() {
+        let mut add = |type_name: &TypeName, field: Option<(String, usize)>| {
+            for target in value_types(type_name) {
+                edges.push(Edge {
+                    source: source.clone(),
+                    target,
+                    field: field.clone(),
+                });
+            }
+        };
+        match type_info {
+            TypeInfo::Object(o) => {
+                for (index, field) in o.fields.iter().enumerate() {
+                    let config = field.config.as_ref();
+                    let wrapper = config.and_then(|c| c.rust_type_wrapper.as_ref());
+                    // a `Cow` owns its value inline, while custom Rust types are none of ours
+                    let has_rust_type = config.and_then(|c| c.rust_type.as_ref()).is_some();
+                    if !has_rust_type && matches!(wrapper, None | Some(RustTypeWrapper::Cow)) {
+                        add(&field.field_type, Some((source.clone(), index)));
+                    }
+                }
+            }
+            TypeInfo::ObjectEnum(e) => {
+                for value in e.values.iter() {
+                    match value {
+                        ObjectEnumValue::Simple(_) => {}
+                        ObjectEnumValue::CustomType(t) => {
+                            add(&TypeName::CustomType(t.clone()), None)
+                        }
+                        ObjectEnumValue::Wrapped(_, t) => add(t, None),
+                    }
+                }
+            }
+            TypeInfo::Alias(a) => add(&a.target_type, None),
+            TypeInfo::Newtype(n) => add(&n.target_type, None),
+            TypeInfo::Enum(_) | TypeInfo::List(_) | TypeInfo::Map(_) => {}
+        }
+    }
+    edges
+}
+
+/// Custom types held inline by a value of `type_name`. Arguments of generic types are assumed
+/// to be held inline as well.
+fn value_types(type_name: &TypeName) -> Vec<String> {
+    match type_name {
+        TypeName::CustomType(name) => vec![name.clone()],
+        TypeName::Optional(item) => value_types(item),
+        TypeName::Generic(_, _) => type_name.custom_types(),
+        _ => vec![],
+    }
+}
+
+fn reachable(from: &str, edges: &[Edge]) -> HashSet<String> {
+    let mut reached = HashSet::new();
+    let mut pending = vec![from.to_owned()];
+    while let Some(current) = pending.pop() {
+        for edge in edges.iter().filter(|e| e.source == current) {
+            if reached.insert(edge.target.clone()) {
+                pending.push(edge.target.clone());
+            }
+        }
+    }
+    reached
+}
+
+fn strongly_connected(type_name: &str, edges: &[Edge]) -> HashSet<String> {
+    let mut component = reachable(type_name, edges)
+        .into_iter()
+        .filter(|t| reachable(t, edges).contains(type_name))
+        .collect::<HashSet<_>>();
+    component.insert(type_name.to_owned());
+    component
+}
+
+/// Whether the edges but the `removed` ones have no cycles.
+fn is_acyclic(edges: &[&Edge], removed: &[usize]) -> bool {
+    let remaining = (0..edges.len())
+        .filter(|i| !removed.contains(i))
+        .map(|i| edges[i])
+        .collect::<Vec<_>>();
+    // repeatedly drops types without incoming edges, which leaves nothing behind if acyclic
+    let mut types = remaining
+        .iter()
+        .flat_map(|e| [e.source.as_str(), e.target.as_str()])
+        .collect::<HashSet<_>>();
+    loop {
+        let roots = types
+            .iter()
+            .filter(|t| {
+                !remaining
+                    .iter()
+                    .any(|e| e.target == **t && types.contains(e.source.as_str()))
+            })
+            .copied()
+            .collect::<Vec<_>>();
+        if roots.is_empty() {
+            return types.is_empty();
+        }
+        for root in roots {
+            types.remove(root);
+        }
+    }
+}
+
+/// The first of the smallest combinations of `candidates` whose removal breaks all cycles.
+fn fewest_edges(edges: &[&Edge], candidates: &[usize]) -> Vec<usize> {
+    fn search(edges: &[&Edge], candidates: &[usize], size: usize, chosen: &mut Vec<usize>) -> bool {
+        if chosen.len() == size {
+            return is_acyclic(edges, chosen);
+        }
+        for (i, candidate) in candidates.iter().enumerate() {
+            chosen.push(*candidate);
+            if search(edges, &candidates[i + 1..], size, chosen) {
+                return true;
+            }
+            chosen.pop();
+        }
+        false
+    }
+    for size in 1..=candidates.len() {
+        let mut chosen = vec![];
+        if search(edges, candidates, size, &mut chosen) {
+            return chosen;
+        }
+    }
+    candidates.to_vec()
+}
+
+/// Starting from all `candidates` removed, restores every edge which doesn't bring back a
+/// cycle, so that no edge in the result could be left out.
+fn minimal_edges(edges: &[&Edge], candidates: &[usize]) -> Vec<usize> {
+    let mut removed = candidates.to_vec();
+    for candidate in candidates {
+        let without = removed
+            .iter()
+            .copied()
+            .filter(|i| i != candidate)
+            .collect::<Vec<_>>();
+        if is_acyclic(edges, &without) {
+            removed = without;
+        }
+    }
+    removed
+}
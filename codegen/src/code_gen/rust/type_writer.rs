@@ -1,19 +1,49 @@
 use crate::{
-    code_gen::abi::{
-        CodeGenContext, EnumTypeInfo, EnumWriter, ListTypeInfo, ListWriter, MapTypeInfo, MapWriter,
-        ObjectEnumTypeInfo, ObjectEnumValue, ObjectEnumWriter, ObjectField, ObjectTypeInfo,
-        ObjectWriter, TypeInfo,
+    code_gen::{
+        abi::{
+            AliasTypeInfo, AliasWriter, CodeGenContext, EnumTypeInfo, EnumWriter, ListTypeInfo,
+            ListWriter, MapTypeInfo, MapWriter, NewtypeTypeInfo, NewtypeWriter, ObjectEnumTagging,
+            ObjectEnumTypeInfo, ObjectEnumValue, ObjectEnumWriter, ObjectField, ObjectTypeInfo,
+            ObjectWriter, TypeInfo, TypeName,
+        },
+        utils::to_snake_case,
     },
-    definitions::ObjectEnumStyle,
+    definitions::{EnumRepresentation, NamingConvention, ObjectEnumStyle, RustTypeWrapper},
 };
 
-use super::RustContext;
-use std::io::Write;
+use super::{
+    serde_field_name, serde_name, serde_rename_rule, serde_variant_name, to_rust_ident,
+    to_rust_variant_ident, RustContext,
+};
+use std::{collections::HashSet, io::Write};
 
 use anyhow::anyhow;
 
 pub struct RustTypeWriter {}
 
+/// How the payload of an object enum variant is laid out, which decides the helpers it gets.
+#[derive(Clone)]
+enum VariantShape<'a> {
+    Unit,
+    /// `Variant(T)`
+    Tuple(String),
+    /// `Variant { content: T }`, with the ident of the content field
+    Content(String, String),
+    /// An object inlined as `Variant { .. }`
+    Inline(&'a ObjectTypeInfo),
+}
+
+impl VariantShape<'_> {
+    /// The pattern matching any payload of the variant.
+    fn wildcard(&self) -> &'static str {
+        match self {
+            VariantShape::Unit => "",
+            VariantShape::Tuple(_) => "(..)",
+            _ => " { .. }",
+        }
+    }
+}
+
 impl ObjectWriter<RustContext> for RustTypeWriter {
     fn write_object(
         &self,
@@ -21,14 +51,46 @@ impl ObjectWriter<RustContext> for RustTypeWriter {
         type_info: &ObjectTypeInfo,
         context: &RustContext,
     ) -> anyhow::Result<()> {
-        writer.write_all(format!("{}\n", context.type_descriptions()).as_bytes())?;
-        writer.write_all(format!("pub struct {} {{\n", type_info.name).as_bytes())?;
+        let object = TypeName::CustomType(format!("{}.{}", type_info.package, type_info.name));
+        let derives = context.eq_derives(&[&object]);
+        let descriptions = context.type_descriptions(&derives, type_info.configs.as_ref());
+        writer.write_all(format!("{}\n", descriptions).as_bytes())?;
+        if !type_info.type_params.is_empty() {
+            let bounds = |bound: &str| {
+                type_info
+                    .type_params
+                    .iter()
+                    .map(|p| format!("{}: {}", p, bound))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            writer.write_all(
+                format!(
+                    "#[serde(bound(serialize = \"{}\", deserialize = \"{}\"))]\n",
+                    bounds("serde::Serialize"),
+                    bounds("serde::Deserialize<'de>")
+                )
+                .as_bytes(),
+            )?;
+        }
+        self.write_rename_all(writer, type_info.rename_all.as_ref())?;
+        writer.write_all(
+            format!(
+                "pub struct {}{} {{\n",
+                type_info.name,
+                self.generics(&type_info.type_params)
+            )
+            .as_bytes(),
+        )?;
         // write fields
+        self.check_field_idents(type_info)?;
         for field in type_info.fields.iter() {
-            self.write_object_field(writer, field, type_info, context)?;
+            self.write_object_field(writer, field, type_info, None, context)?;
         }
         writer.write_all("}\n".as_bytes())?;
         writer.write_all("\n".as_bytes())?;
+        self.write_default_values(writer, type_info, None, context)?;
+        self.write_builder(writer, type_info, context)?;
         Ok(())
     }
 }
@@ -40,14 +102,47 @@ impl EnumWriter<RustContext> for RustTypeWriter {
         enum_type_info: &EnumTypeInfo,
         context: &RustContext,
     ) -> anyhow::Result<()> {
-        writer.write_all(format!("{}\n", context.type_descriptions()).as_bytes())?;
-        writer.write_all(format!("pub enum {} {{\n", enum_type_info.name).as_bytes())?;
+        let name = &enum_type_info.name;
+        let rename_all = enum_type_info.rename_all.as_ref();
+        // unknown values are kept as strings, which can't be copied; `Copy` and the comparisons
+        // are left out unless `Clone` and `PartialEq` are derived
+        let derives = match enum_type_info.allow_unknown {
+            true => vec!["Eq", "Hash", "PartialOrd", "Ord"],
+            _ => vec!["Copy", "Eq", "Hash", "PartialOrd", "Ord"],
+        };
+        let descriptions = context.type_descriptions(&derives, enum_type_info.configs.as_ref());
+        writer.write_all(format!("{}\n", descriptions).as_bytes())?;
+        let is_int = enum_type_info.representation == EnumRepresentation::Int;
+        match is_int {
+            true => writer.write_all(
+                "#[serde(into = \"i32\", try_from = \"i32\")]\n#[repr(i32)]\n".as_bytes(),
+            )?,
+            _ => self.write_rename_all(writer, rename_all)?,
+        }
+        writer.write_all(format!("pub enum {} {{\n", name).as_bytes())?;
         // write values
-        for value in enum_type_info.values.iter() {
-            writer.write_all(format!("  {},\n", value).as_bytes())?;
+        for (index, value) in enum_type_info.values.iter().enumerate() {
+            let discriminant = enum_type_info.discriminants.as_ref().map(|d| d[index]);
+            match (is_int, discriminant) {
+                // names of integer enums are not on the wire
+                (true, Some(d)) => writer.write_all(
+                    format!("  {} = {},\n", to_rust_variant_ident(value), d).as_bytes(),
+                )?,
+                _ => {
+                    let wire_name = self.wire_name(value, rename_all);
+                    self.write_variant(writer, value, &wire_name, discriminant, rename_all)?
+                }
+            }
+        }
+        if enum_type_info.allow_unknown {
+            self.write_unknown_variant(writer, name, &enum_type_info.values, "String", true)?;
         }
         writer.write_all("}\n".as_bytes())?;
         writer.write_all("\n".as_bytes())?;
+        if is_int {
+            self.write_int_enum_conversions(writer, enum_type_info)?;
+        }
+        self.write_enum_names(writer, enum_type_info)?;
         Ok(())
     }
 }
@@ -64,54 +159,139 @@ impl ObjectEnumWriter<RustContext> for RustTypeWriter {
             .clone()
             .and_then(|c| c.object_enum_style)
             .unwrap_or(ObjectEnumStyle::Inline);
-        writer.write_all(format!("{}\n", context.type_descriptions()).as_bytes())?;
-        writer.write_all(
-            format!("#[serde(tag = \"{}\")]\n", object_enum_type_info.type_tag).as_bytes(),
-        )?;
+        let object_enum = TypeName::CustomType(format!(
+            "{}.{}",
+            object_enum_type_info.package, object_enum_type_info.name
+        ));
+        let derives = context.eq_derives(&[&object_enum]);
+        let configs = object_enum_type_info.configs.as_ref();
+        writer
+            .write_all(format!("{}\n", context.type_descriptions(&derives, configs)).as_bytes())?;
+        match &object_enum_type_info.tagging {
+            ObjectEnumTagging::Internal { tag, .. } => {
+                writer.write_all(format!("#[serde(tag = {:?})]\n", tag).as_bytes())?
+            }
+            ObjectEnumTagging::Adjacent { tag, content } => writer.write_all(
+                format!("#[serde(tag = {:?}, content = {:?})]\n", tag, content).as_bytes(),
+            )?,
+            ObjectEnumTagging::External => {}
+            ObjectEnumTagging::Untagged => writer.write_all("#[serde(untagged)]\n".as_bytes())?,
+        }
+        let rename_all = object_enum_type_info.rename_all.as_ref();
+        self.write_rename_all(writer, rename_all)?;
         writer.write_all(format!("pub enum {} {{\n", object_enum_type_info.name).as_bytes())?;
 
+        let tagging = &object_enum_type_info.tagging;
+        let mut variants = vec![];
+        let mut wrapped_types = vec![];
         for value in object_enum_type_info.values.iter() {
             match value {
                 ObjectEnumValue::Simple(simple) => {
-                    writer.write_all(format!("  {},\n", simple).as_bytes())?;
-                }
-                ObjectEnumValue::CustomType(type_name) if enum_style == ObjectEnumStyle::Extern => {
-                    match context.type_dict().get(type_name) {
-                        Some(t) => {
-                            writer.write_all(
-                                format!("  {}({}),\n", t.type_name(), context.get_fqn_for_type(t))
-                                    .as_bytes(),
-                            )?;
-                        }
-                        _ => {
-                            return Err(anyhow!(
-                                "Enum cannot be nested within enum object: {}",
-                                type_name
-                            ));
-                        }
-                    }
+                    let tag = object_enum_type_info.variant_tag(simple);
+                    self.write_variant(writer, simple, &tag, None, rename_all)?;
+                    variants.push((to_rust_variant_ident(simple), VariantShape::Unit));
                 }
                 ObjectEnumValue::CustomType(type_name) => {
-                    match context.type_dict().get(type_name) {
-                        Some(TypeInfo::Object(type_info)) => {
+                    match (&enum_style, context.type_dict().get(type_name)) {
+                        (ObjectEnumStyle::Inline, Some(TypeInfo::Object(type_info)))
+                            if type_info.type_params.is_empty() =>
+                        {
+                            self.check_field_idents(type_info)?;
+                            let name = &type_info.name;
+                            let tag = object_enum_type_info.variant_tag(name);
+                            self.write_variant_rename(writer, &tag, name, rename_all)?;
                             writer.write_all(format!("  {} {{\n", type_info.name).as_bytes())?;
                             for field in &type_info.fields {
-                                self.write_object_field(writer, field, type_info, context)?;
+                                let variant_of = Some(object_enum_type_info.name.as_str());
+                                self.write_object_field(
+                                    writer, field, type_info, variant_of, context,
+                                )?;
                             }
                             writer.write_all("  },\n".as_bytes())?;
+                            variants.push((name.clone(), VariantShape::Inline(type_info)));
                         }
-                        _ => {
-                            return Err(anyhow!(
-                                "Enum cannot be nested within enum object: {}",
-                                type_name
-                            ));
+                        (_, Some(t)) => {
+                            let name = t.type_name();
+                            let tag = object_enum_type_info.variant_tag(name);
+                            self.write_variant_rename(writer, &tag, name, rename_all)?;
+                            let payload = TypeName::CustomType(type_name.clone());
+                            let shape = self
+                                .write_wrapped_variant(writer, name, &payload, tagging, context)?;
+                            wrapped_types.push((name.to_owned(), shape.clone()));
+                            variants.push((name.to_owned(), shape));
+                        }
+                        (_, None) => {
+                            return Err(anyhow!("Cannot find variant type: {}", type_name));
                         }
                     }
                 }
+                ObjectEnumValue::Wrapped(name, payload) => {
+                    let ident = to_rust_variant_ident(name);
+                    let tag = object_enum_type_info.variant_tag(name);
+                    self.write_variant_rename(writer, &tag, &ident, rename_all)?;
+                    let shape =
+                        self.write_wrapped_variant(writer, &ident, payload, tagging, context)?;
+                    variants.push((ident, shape));
+                }
             }
         }
+        if object_enum_type_info
+            .configs
+            .as_ref()
+            .and_then(|c| c.allow_unknown)
+            .unwrap_or(false)
+        {
+            let values = object_enum_type_info
+                .values
+                .iter()
+                .map(|v| match v {
+                    ObjectEnumValue::Simple(name) | ObjectEnumValue::Wrapped(name, _) => {
+                        name.clone()
+                    }
+                    ObjectEnumValue::CustomType(type_name) => context
+                        .type_dict()
+                        .get(type_name)
+                        .map(|t| t.type_name().to_owned())
+                        .unwrap_or_default(),
+                })
+                .collect::<Vec<_>>();
+            let any_type = context.get_fully_qualified_type_name(&TypeName::Any)?;
+            // the last variant of an untagged enum catches everything anyway
+            let is_untagged = object_enum_type_info.tagging == ObjectEnumTagging::Untagged;
+            self.write_unknown_variant(
+                writer,
+                &object_enum_type_info.name,
+                &values,
+                &any_type,
+                !is_untagged,
+            )?;
+            variants.push(("Unknown".to_owned(), VariantShape::Tuple(any_type)));
+        }
         writer.write_all("}".as_bytes())?;
         writer.write_all("\n".as_bytes())?;
+        self.write_object_enum_helpers(writer, object_enum_type_info, &variants, context)?;
+        for (ident, shape) in wrapped_types.iter() {
+            self.write_wrapped_conversion(writer, &object_enum_type_info.name, ident, shape)?;
+        }
+
+        if enum_style != ObjectEnumStyle::Extern {
+            for value in object_enum_type_info.values.iter() {
+                if let ObjectEnumValue::CustomType(type_name) = value {
+                    if let Some(TypeInfo::Object(type_info)) = context.type_dict().get(type_name) {
+                        let variant_of = Some(object_enum_type_info.name.as_str());
+                        self.write_default_values(writer, type_info, variant_of, context)?;
+                        if type_info.is_standalone {
+                            self.write_variant_conversions(
+                                writer,
+                                object_enum_type_info,
+                                type_info,
+                                context,
+                            )?;
+                        }
+                    }
+                }
+            }
+        }
         Ok(())
     }
 }
@@ -153,53 +333,875 @@ impl ListWriter<RustContext> for RustTypeWriter {
     }
 }
 
+impl AliasWriter<RustContext> for RustTypeWriter {
+    fn write_alias(
+        &self,
+        writer: &mut dyn Write,
+        type_info: &AliasTypeInfo,
+        context: &RustContext,
+    ) -> anyhow::Result<()> {
+        let target_type = context.get_fully_qualified_type_name(&type_info.target_type)?;
+        writer.write_all(format!("pub type {} = {};\n", type_info.name, target_type).as_bytes())?;
+        writer.write_all("\n".as_bytes())?;
+        Ok(())
+    }
+}
+
+impl NewtypeWriter<RustContext> for RustTypeWriter {
+    fn write_newtype(
+        &self,
+        writer: &mut dyn Write,
+        type_info: &NewtypeTypeInfo,
+        context: &RustContext,
+    ) -> anyhow::Result<()> {
+        let name = &type_info.name;
+        let target_type = context.get_fully_qualified_type_name(&type_info.target_type)?;
+        let derives = context.eq_derives(&[&type_info.target_type]);
+        writer.write_all(format!("{}\n", context.type_descriptions(&derives, None)).as_bytes())?;
+        writer.write_all("#[serde(transparent)]\n".as_bytes())?;
+        writer.write_all(format!("pub struct {}(pub {});\n\n", name, target_type).as_bytes())?;
+
+        writer.write_all(format!("impl From<{}> for {} {{\n", target_type, name).as_bytes())?;
+        writer.write_all(format!("  fn from(value: {}) -> Self {{\n", target_type).as_bytes())?;
+        writer.write_all("    Self(value)\n  }\n}\n\n".as_bytes())?;
+
+        writer.write_all(format!("impl From<{}> for {} {{\n", name, target_type).as_bytes())?;
+        writer.write_all(format!("  fn from(value: {}) -> Self {{\n", name).as_bytes())?;
+        writer.write_all("    value.0\n  }\n}\n\n".as_bytes())?;
+
+        writer.write_all(format!("impl std::ops::Deref for {} {{\n", name).as_bytes())?;
+        writer.write_all(format!("  type Target = {};\n\n", target_type).as_bytes())?;
+        writer.write_all("  fn deref(&self) -> &Self::Target {\n".as_bytes())?;
+        writer.write_all("    &self.0\n  }\n}\n\n".as_bytes())?;
+        Ok(())
+    }
+}
+
 impl RustTypeWriter {
     fn write_object_field(
         &self,
         writer: &mut dyn Write,
         field: &ObjectField,
         type_info: &ObjectTypeInfo,
+        variant_of: Option<&str>,
         context: &RustContext,
     ) -> anyhow::Result<()> {
-        let type_to_write = context.get_fully_qualified_type_name(&field.field_type)?;
+        let type_to_write = self.get_field_type(field, context)?;
+        let (indent, visibility) = match variant_of {
+            Some(_) => ("    ", ""),
+            None => ("  ", "pub "),
+        };
+        if field.default.is_some() {
+            writer.write_all(
+                format!(
+                    "{}#[serde(default = \"{}\")]\n",
+                    indent,
+                    self.default_fn_name(field, type_info, variant_of)
+                )
+                .as_bytes(),
+            )?;
+        }
+        // inline variants of object enums are not covered by the `rename_all` of their objects
+        let rename_all = match variant_of {
+            Some(_) => None,
+            None => type_info.rename_all.as_ref(),
+        };
+        let ident = self.field_ident(field);
+        let wire_name = self.wire_name(&field.name, type_info.rename_all.as_ref());
+        if serde_field_name(&ident, rename_all) != wire_name {
+            writer
+                .write_all(format!("{}#[serde(rename = {:?})]\n", indent, wire_name).as_bytes())?;
+        }
+        if let Some(module) = field.config.as_ref().and_then(|c| c.serde_with.as_ref()) {
+            writer.write_all(format!("{}#[serde(with = \"{}\")]\n", indent, module).as_bytes())?;
+        }
+        let attributes = field.config.as_ref().and_then(|c| c.attributes.as_ref());
+        for attribute in attributes.into_iter().flatten() {
+            writer.write_all(format!("{}#[{}]\n", indent, attribute).as_bytes())?;
+        }
+        writer.write_all(
+            format!("{}{}{}: {},\n", indent, visibility, ident, type_to_write).as_bytes(),
+        )?;
+        Ok(())
+    }
+
+    fn get_field_type(&self, field: &ObjectField, context: &RustContext) -> anyhow::Result<String> {
+        let type_to_write = self.get_field_base_type(field, context)?;
         let type_to_write = match field.is_optional() {
             true => format!("Option<{}>", type_to_write),
             _ => type_to_write,
         };
-        let type_to_write = match &field
+        let type_to_write = match field
             .config
             .as_ref()
-            .and_then(|c| c.rust_type_wrapper.clone())
+            .and_then(|c| c.rust_type_wrapper.as_ref())
         {
-            Some(_) => format!("Box<{}>", type_to_write),
+            Some(wrapper) => self.wrapped_type(wrapper, &type_to_write),
             _ => type_to_write,
         };
-        match &field.config.as_ref().and_then(|c| c.rename.clone()) {
-            Some(rename) => {
-                if type_info.is_object_enum_value {
-                    writer.write_all(
-                        format!("    #[serde(rename = \"{}\")]\n", field.name).as_bytes(),
-                    )?;
-                    writer.write_all(format!("    {}: {},\n", rename, type_to_write).as_bytes())?;
-                } else {
-                    writer.write_all(
-                        format!("  #[serde(rename = \"{}\")]\n", field.name).as_bytes(),
-                    )?;
+        Ok(type_to_write)
+    }
+
+    /// The type of a field with its items wrapped, but without `Option` or a wrapper of its own.
+    fn get_field_base_type(
+        &self,
+        field: &ObjectField,
+        context: &RustContext,
+    ) -> anyhow::Result<String> {
+        if let Some(rust_type) = field.config.as_ref().and_then(|c| c.rust_type.as_ref()) {
+            return Ok(rust_type.clone());
+        }
+        let type_to_write = context.get_fully_qualified_type_name(&field.field_type)?;
+        let item_wrapper = field
+            .config
+            .as_ref()
+            .and_then(|c| c.rust_item_wrapper.as_ref());
+        let wrap_item = |item: &TypeName, wrapper: &RustTypeWrapper| {
+            let item = context.get_fully_qualified_type_name(item)?;
+            anyhow::Ok(self.wrapped_type(wrapper, &item))
+        };
+        let type_to_write = match (item_wrapper, &field.field_type) {
+            (None, _) => type_to_write,
+            (Some(wrapper), TypeName::List(item)) => format!("Vec<{}>", wrap_item(item, wrapper)?),
+            (Some(wrapper), TypeName::Set(item)) => {
+                format!("std::collections::HashSet<{}>", wrap_item(item, wrapper)?)
+            }
+            (Some(wrapper), TypeName::Map(key, value)) => format!(
+                "std::collections::HashMap<{}, {}>",
+                context.get_fully_qualified_type_name(key)?,
+                wrap_item(value, wrapper)?
+            ),
+            (Some(_), t) => return Err(anyhow!("Cannot wrap items of {}: {}", field.name, t)),
+        };
+        Ok(type_to_write)
+    }
+
+    fn wrapped_type(&self, wrapper: &RustTypeWrapper, type_to_write: &str) -> String {
+        match wrapper {
+            RustTypeWrapper::Box => format!("Box<{}>", type_to_write),
+            RustTypeWrapper::Rc => format!("std::rc::Rc<{}>", type_to_write),
+            RustTypeWrapper::Arc => format!("std::sync::Arc<{}>", type_to_write),
+            // strings are borrowed as `str`, as with `&'static str` literals
+            RustTypeWrapper::Cow => match type_to_write {
+                "String" => "std::borrow::Cow<'static, str>".to_owned(),
+                _ => format!("std::borrow::Cow<'static, {}>", type_to_write),
+            },
+        }
+    }
+
+    fn wrapped_value(&self, wrapper: &RustTypeWrapper, value: &str) -> String {
+        match wrapper {
+            RustTypeWrapper::Box => format!("Box::new({})", value),
+            RustTypeWrapper::Rc => format!("std::rc::Rc::new({})", value),
+            RustTypeWrapper::Arc => format!("std::sync::Arc::new({})", value),
+            RustTypeWrapper::Cow => format!("std::borrow::Cow::Owned({})", value),
+        }
+    }
+
+    fn generics(&self, type_params: &[String]) -> String {
+        match type_params.is_empty() {
+            true => "".to_owned(),
+            _ => format!("<{}>", type_params.join(", ")),
+        }
+    }
+
+    fn field_ident(&self, field: &ObjectField) -> String {
+        match field.config.as_ref().and_then(|c| c.rename.as_ref()) {
+            Some(rename) => to_rust_ident(rename),
+            None => to_rust_ident(&to_snake_case(&field.name)),
+        }
+    }
+
+    fn wire_name(&self, name: &str, rename_all: Option<&NamingConvention>) -> String {
+        match rename_all {
+            Some(convention) => convention.apply(name),
+            None => name.to_owned(),
+        }
+    }
+
+    fn write_rename_all(
+        &self,
+        writer: &mut dyn Write,
+        rename_all: Option<&NamingConvention>,
+    ) -> anyhow::Result<()> {
+        if let Some(convention) = rename_all {
+            writer.write_all(
+                format!(
+                    "#[serde(rename_all = \"{}\")]\n",
+                    serde_rename_rule(convention)
+                )
+                .as_bytes(),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Writes `#[serde(rename = "...")]` for a variant when its `rename_all` rule doesn't yield
+    /// the expected name.
+    fn write_variant_rename(
+        &self,
+        writer: &mut dyn Write,
+        wire_name: &str,
+        ident: &str,
+        rename_all: Option<&NamingConvention>,
+    ) -> anyhow::Result<()> {
+        if serde_variant_name(ident, rename_all) != wire_name {
+            writer.write_all(format!("  #[serde(rename = {:?})]\n", wire_name).as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn write_variant(
+        &self,
+        writer: &mut dyn Write,
+        value: &str,
+        wire_name: &str,
+        discriminant: Option<i32>,
+        rename_all: Option<&NamingConvention>,
+    ) -> anyhow::Result<()> {
+        let ident = to_rust_variant_ident(value);
+        self.write_variant_rename(writer, wire_name, &ident, rename_all)?;
+        match discriminant {
+            Some(discriminant) => {
+                writer.write_all(format!("  {} = {},\n", ident, discriminant).as_bytes())?
+            }
+            None => writer.write_all(format!("  {},\n", ident).as_bytes())?,
+        }
+        Ok(())
+    }
+
+    /// Converts between a standalone object and the inline variant of an object enum for it.
+    fn write_variant_conversions(
+        &self,
+        writer: &mut dyn Write,
+        object_enum_type_info: &ObjectEnumTypeInfo,
+        type_info: &ObjectTypeInfo,
+        context: &RustContext,
+    ) -> anyhow::Result<()> {
+        let object_enum = &object_enum_type_info.name;
+        let object = context.get_fqn_for_type(&TypeInfo::Object(type_info.clone()));
+        let variant = format!("{}::{}", object_enum, type_info.name);
+        let fields = type_info
+            .fields
+            .iter()
+            .map(|f| self.field_ident(f))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        writer.write_all(format!("impl From<{}> for {} {{\n", object, object_enum).as_bytes())?;
+        writer.write_all(format!("  fn from(value: {}) -> Self {{\n", object).as_bytes())?;
+        writer.write_all(format!("    let {} {{ {} }} = value;\n", object, fields).as_bytes())?;
+        writer.write_all(format!("    {} {{ {} }}\n  }}\n}}\n\n", variant, fields).as_bytes())?;
+
+        writer
+            .write_all(format!("impl TryFrom<{}> for {} {{\n", object_enum, object).as_bytes())?;
+        writer.write_all(format!("  type Error = {};\n\n", object_enum).as_bytes())?;
+        writer.write_all(
+            format!(
+                "  fn try_from(value: {}) -> Result<Self, Self::Error> {{\n",
+                object_enum
+            )
+            .as_bytes(),
+        )?;
+        writer.write_all("    #[allow(unreachable_patterns)]\n    match value {\n".as_bytes())?;
+        writer.write_all(
+            format!(
+                "      {} {{ {} }} => Ok({} {{ {} }}),\n",
+                variant, fields, object, fields
+            )
+            .as_bytes(),
+        )?;
+        writer.write_all("      other => Err(other),\n    }\n  }\n}\n\n".as_bytes())?;
+        Ok(())
+    }
+
+    /// Writes a variant carrying a payload, which is nested under the content key of internally
+    /// tagged enums unless it is an object itself.
+    fn write_wrapped_variant(
+        &self,
+        writer: &mut dyn Write,
+        ident: &str,
+        payload: &TypeName,
+        tagging: &ObjectEnumTagging,
+        context: &RustContext,
+    ) -> anyhow::Result<VariantShape<'static>> {
+        let payload_type = context.get_fully_qualified_type_name(payload)?;
+        match tagging {
+            ObjectEnumTagging::Internal { tag, content }
+                if !self.is_object(payload, tag, context) =>
+            {
+                let field = to_rust_ident(&to_snake_case(content));
+                writer.write_all(format!("  {} {{\n", ident).as_bytes())?;
+                if serde_name(&field) != content {
                     writer
-                        .write_all(format!("  pub {}: {},\n", rename, type_to_write).as_bytes())?;
+                        .write_all(format!("    #[serde(rename = {:?})]\n", content).as_bytes())?;
                 }
+                writer.write_all(format!("    {}: {},\n", field, payload_type).as_bytes())?;
+                writer.write_all("  },\n".as_bytes())?;
+                Ok(VariantShape::Content(field, payload_type))
+            }
+            _ => {
+                writer.write_all(format!("  {}({}),\n", ident, payload_type).as_bytes())?;
+                Ok(VariantShape::Tuple(payload_type))
             }
-            None => {
-                if type_info.is_object_enum_value {
-                    writer.write_all(
-                        format!("    {}: {},\n", field.name, type_to_write).as_bytes(),
-                    )?;
-                } else {
-                    writer.write_all(
-                        format!("  pub {}: {},\n", field.name, type_to_write).as_bytes(),
-                    )?;
+        }
+    }
+
+    /// Writes `is_*`, `as_*`, `as_*_mut` and `into_*` accessors per variant, and `kind()`
+    /// returning a fieldless `{Name}Kind` enum of the variants.
+    fn write_object_enum_helpers(
+        &self,
+        writer: &mut dyn Write,
+        object_enum_type_info: &ObjectEnumTypeInfo,
+        variants: &[(String, VariantShape)],
+        context: &RustContext,
+    ) -> anyhow::Result<()> {
+        let name = &object_enum_type_info.name;
+        let kind = format!("{}Kind", name);
+        let kind_fqn = format!("{}.{}", object_enum_type_info.package, kind);
+        if context.type_dict().contains_key(&kind_fqn) {
+            return Err(anyhow!(
+                "Type {} collides with the kind enum generated for {}",
+                kind_fqn,
+                name
+            ));
+        }
+
+        writer.write_all("\n#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]\n".as_bytes())?;
+        writer.write_all(format!("pub enum {} {{\n", kind).as_bytes())?;
+        for (ident, _) in variants {
+            writer.write_all(format!("  {},\n", ident).as_bytes())?;
+        }
+        writer.write_all("}\n\n".as_bytes())?;
+
+        writer.write_all(format!("impl {} {{\n", name).as_bytes())?;
+        writer.write_all(format!("  pub fn kind(&self) -> {} {{\n", kind).as_bytes())?;
+        writer.write_all("    match self {\n".as_bytes())?;
+        for (ident, shape) in variants {
+            writer.write_all(
+                format!(
+                    "      {}::{}{} => {}::{},\n",
+                    name,
+                    ident,
+                    shape.wildcard(),
+                    kind,
+                    ident
+                )
+                .as_bytes(),
+            )?;
+        }
+        writer.write_all("    }\n  }\n".as_bytes())?;
+
+        for (ident, shape) in variants {
+            let method = to_snake_case(ident);
+            writer.write_all(
+                format!(
+                    "\n  pub fn is_{}(&self) -> bool {{\n    matches!(self, {}::{}{})\n  }}\n",
+                    method,
+                    name,
+                    ident,
+                    shape.wildcard()
+                )
+                .as_bytes(),
+            )?;
+            // the values bound by the pattern of a variant, with their types
+            let (pattern, bindings, object) = match shape {
+                VariantShape::Unit => continue,
+                VariantShape::Tuple(payload_type) => (
+                    format!("{}::{}(value)", name, ident),
+                    vec![("value".to_owned(), payload_type.clone())],
+                    None,
+                ),
+                VariantShape::Content(field, payload_type) => (
+                    format!("{}::{} {{ {}: value }}", name, ident, field),
+                    vec![("value".to_owned(), payload_type.clone())],
+                    None,
+                ),
+                VariantShape::Inline(type_info) => {
+                    let bindings = type_info
+                        .fields
+                        .iter()
+                        .map(|f| Ok((self.field_ident(f), self.get_field_type(f, context)?)))
+                        .collect::<anyhow::Result<Vec<_>>>()?;
+                    let fields = bindings
+                        .iter()
+                        .map(|(field, _)| field.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    let fields = match fields.is_empty() {
+                        true => "{}".to_owned(),
+                        _ => format!("{{ {} }}", fields),
+                    };
+                    // inline objects only exist as values of their own when they are standalone
+                    let object = type_info.is_standalone.then(|| {
+                        let object =
+                            context.get_fqn_for_type(&TypeInfo::Object((*type_info).clone()));
+                        (format!("{} {}", object, fields), object)
+                    });
+                    (format!("{}::{} {}", name, ident, fields), bindings, object)
+                }
+            };
+            // a single value is returned as is, several ones as a tuple
+            let payload = |reference: &str| match bindings.as_slice() {
+                [(value, payload_type)] => {
+                    (format!("{}{}", reference, payload_type), value.clone())
                 }
+                _ => (
+                    format!(
+                        "({})",
+                        bindings
+                            .iter()
+                            .map(|(_, t)| format!("{}{}", reference, t))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    format!(
+                        "({})",
+                        bindings
+                            .iter()
+                            .map(|(v, _)| v.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                ),
+            };
+            for (suffix, reference) in [("", "&"), ("_mut", "&mut ")] {
+                let (payload_type, value) = payload(reference);
+                writer.write_all(
+                    format!(
+                        "\n  pub fn as_{}{}({}self) -> Option<{}> {{\n",
+                        method, suffix, reference, payload_type
+                    )
+                    .as_bytes(),
+                )?;
+                writer.write_all(
+                    format!("    match self {{\n      {} => Some({}),\n", pattern, value)
+                        .as_bytes(),
+                )?;
+                writer.write_all(
+                    "      #[allow(unreachable_patterns)]\n      _ => None,\n    }\n  }\n"
+                        .as_bytes(),
+                )?;
+            }
+            let (value, payload_type) = object.unwrap_or_else(|| {
+                let (payload_type, value) = payload("");
+                (value, payload_type)
+            });
+            writer.write_all(
+                format!(
+                    "\n  pub fn into_{}(self) -> Result<{}, Self> {{\n",
+                    method, payload_type
+                )
+                .as_bytes(),
+            )?;
+            writer.write_all(
+                format!("    match self {{\n      {} => Ok({}),\n", pattern, value).as_bytes(),
+            )?;
+            writer.write_all(
+                "      #[allow(unreachable_patterns)]\n      other => Err(other),\n    }\n  }\n"
+                    .as_bytes(),
+            )?;
+        }
+        writer.write_all("}\n\n".as_bytes())?;
+        Ok(())
+    }
+
+    /// Wraps a custom type into the variant named after it.
+    fn write_wrapped_conversion(
+        &self,
+        writer: &mut dyn Write,
+        object_enum: &str,
+        ident: &str,
+        shape: &VariantShape,
+    ) -> anyhow::Result<()> {
+        let (payload_type, variant) = match shape {
+            VariantShape::Tuple(payload_type) => {
+                (payload_type, format!("{}::{}(value)", object_enum, ident))
+            }
+            VariantShape::Content(field, payload_type) => (
+                payload_type,
+                format!("{}::{} {{ {}: value }}", object_enum, ident, field),
+            ),
+            _ => return Ok(()),
+        };
+        writer.write_all(
+            format!("impl From<{}> for {} {{\n", payload_type, object_enum).as_bytes(),
+        )?;
+        writer.write_all(format!("  fn from(value: {}) -> Self {{\n", payload_type).as_bytes())?;
+        writer.write_all(format!("    {}\n  }}\n}}\n\n", variant).as_bytes())?;
+        Ok(())
+    }
+
+    /// Whether a type is serialized as an object the `tag` of an internally tagged variant can be
+    /// added to. Object enums tagged with the same key are nested instead, as the tag would
+    /// appear twice.
+    fn is_object(&self, type_name: &TypeName, tag: &str, context: &RustContext) -> bool {
+        let TypeName::CustomType(name) = type_name else {
+            return false;
+        };
+        match context.type_dict().get(name) {
+            Some(TypeInfo::Object(_)) => true,
+            Some(TypeInfo::ObjectEnum(e)) => match &e.tagging {
+                ObjectEnumTagging::Internal { tag: inner, .. } => inner != tag,
+                ObjectEnumTagging::Adjacent {
+                    tag: inner,
+                    content,
+                } => inner != tag && content != tag,
+                ObjectEnumTagging::External => true,
+                ObjectEnumTagging::Untagged => false,
+            },
+            Some(TypeInfo::Alias(AliasTypeInfo { target_type, .. }))
+            | Some(TypeInfo::Newtype(NewtypeTypeInfo { target_type, .. })) => {
+                self.is_object(target_type, tag, context)
+            }
+            _ => false,
+        }
+    }
+
+    /// Writes the catch-all variant keeping values unknown to this version as they are.
+    fn write_unknown_variant(
+        &self,
+        writer: &mut dyn Write,
+        name: &str,
+        values: &[String],
+        payload_type: &str,
+        is_tagged: bool,
+    ) -> anyhow::Result<()> {
+        if let Some(value) = values
+            .iter()
+            .find(|v| to_rust_variant_ident(v) == "Unknown")
+        {
+            return Err(anyhow!(
+                "Value {} of {} collides with the variant for unknown values",
+                value,
+                name
+            ));
+        }
+        if is_tagged {
+            writer.write_all("  #[serde(untagged)]\n".as_bytes())?;
+        }
+        writer.write_all(format!("  Unknown({}),\n", payload_type).as_bytes())?;
+        Ok(())
+    }
+
+    /// Writes `ALL`, `as_str()`, `Display` and `FromStr` of an enum, going by the names of its
+    /// values even for integer enums.
+    fn write_enum_names(
+        &self,
+        writer: &mut dyn Write,
+        type_info: &EnumTypeInfo,
+    ) -> anyhow::Result<()> {
+        let name = &type_info.name;
+        let rename_all = type_info.rename_all.as_ref();
+        let values = type_info
+            .values
+            .iter()
+            .map(|v| (to_rust_variant_ident(v), self.wire_name(v, rename_all)))
+            .collect::<Vec<_>>();
+
+        writer.write_all(format!("impl {} {{\n", name).as_bytes())?;
+        writer.write_all("  pub const ALL: &'static [Self] = &[\n".as_bytes())?;
+        for (ident, _) in values.iter() {
+            writer.write_all(format!("    {}::{},\n", name, ident).as_bytes())?;
+        }
+        writer.write_all("  ];\n\n".as_bytes())?;
+        let lifetime = match type_info.allow_unknown {
+            true => "",
+            _ => "'static ",
+        };
+        writer.write_all(format!("  pub fn as_str(&self) -> &{}str {{\n", lifetime).as_bytes())?;
+        writer.write_all("    match self {\n".as_bytes())?;
+        for (ident, wire_name) in values.iter() {
+            writer
+                .write_all(format!("      {}::{} => {:?},\n", name, ident, wire_name).as_bytes())?;
+        }
+        if type_info.allow_unknown {
+            writer.write_all(format!("      {}::Unknown(value) => value,\n", name).as_bytes())?;
+        }
+        writer.write_all("    }\n  }\n}\n\n".as_bytes())?;
+
+        writer.write_all(format!("impl std::fmt::Display for {} {{\n", name).as_bytes())?;
+        writer.write_all(
+            "  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {\n".as_bytes(),
+        )?;
+        writer.write_all("    f.write_str(self.as_str())\n  }\n}\n\n".as_bytes())?;
+
+        writer.write_all(format!("impl std::str::FromStr for {} {{\n", name).as_bytes())?;
+        writer.write_all("  type Err = fluorite::ParseEnumError;\n\n".as_bytes())?;
+        writer.write_all("  fn from_str(s: &str) -> Result<Self, Self::Err> {\n".as_bytes())?;
+        writer.write_all("    match s {\n".as_bytes())?;
+        for (ident, wire_name) in values.iter() {
+            writer.write_all(
+                format!("      {:?} => Ok({}::{}),\n", wire_name, name, ident).as_bytes(),
+            )?;
+        }
+        match type_info.allow_unknown {
+            true => writer.write_all(
+                format!("      _ => Ok({}::Unknown(s.to_owned())),\n", name).as_bytes(),
+            )?,
+            _ => writer.write_all(
+                format!(
+                    "      _ => Err(fluorite::ParseEnumError::new(\"{}\", s)),\n",
+                    name
+                )
+                .as_bytes(),
+            )?,
+        }
+        writer.write_all("    }\n  }\n}\n\n".as_bytes())?;
+        Ok(())
+    }
+
+    /// Integer enums are (de)serialized through their discriminants.
+    fn write_int_enum_conversions(
+        &self,
+        writer: &mut dyn Write,
+        type_info: &EnumTypeInfo,
+    ) -> anyhow::Result<()> {
+        let name = &type_info.name;
+        writer.write_all(format!("impl From<{}> for i32 {{\n", name).as_bytes())?;
+        writer.write_all(format!("  fn from(value: {}) -> Self {{\n", name).as_bytes())?;
+        writer.write_all("    value as i32\n  }\n}\n\n".as_bytes())?;
+
+        writer.write_all(format!("impl TryFrom<i32> for {} {{\n", name).as_bytes())?;
+        writer.write_all("  type Error = String;\n\n".as_bytes())?;
+        writer
+            .write_all("  fn try_from(value: i32) -> Result<Self, Self::Error> {\n".as_bytes())?;
+        writer.write_all("    match value {\n".as_bytes())?;
+        let discriminants = type_info.discriminants.as_deref().unwrap_or_default();
+        for (value, discriminant) in type_info.values.iter().zip(discriminants) {
+            writer.write_all(
+                format!(
+                    "      {} => Ok({}::{}),\n",
+                    discriminant,
+                    name,
+                    to_rust_variant_ident(value)
+                )
+                .as_bytes(),
+            )?;
+        }
+        writer.write_all(
+            format!(
+                "      _ => Err(format!(\"invalid {} value {{}}\", value)),\n",
+                name
+            )
+            .as_bytes(),
+        )?;
+        writer.write_all("    }\n  }\n}\n\n".as_bytes())?;
+        Ok(())
+    }
+
+    /// Writes `{Name}Builder` with a setter per field, whose `build()` fails on required fields
+    /// left unset while optional ones default to `None`.
+    fn write_builder(
+        &self,
+        writer: &mut dyn Write,
+        type_info: &ObjectTypeInfo,
+        context: &RustContext,
+    ) -> anyhow::Result<()> {
+        let name = &type_info.name;
+        let builder = format!("{}Builder", name);
+        let builder_fqn = format!("{}.{}", type_info.package, builder);
+        if context.type_dict().contains_key(&builder_fqn) {
+            return Err(anyhow!(
+                "Type {} collides with the builder generated for {}",
+                builder_fqn,
+                name
+            ));
+        }
+        if let Some(field) = type_info
+            .fields
+            .iter()
+            .find(|f| self.field_ident(f) == "build")
+        {
+            return Err(anyhow!(
+                "Field {} of {} collides with the build() of its builder",
+                field.name,
+                name
+            ));
+        }
+        let generics = self.generics(&type_info.type_params);
+
+        writer.write_all(format!("pub struct {}{} {{\n", builder, generics).as_bytes())?;
+        for field in type_info.fields.iter() {
+            writer.write_all(
+                format!(
+                    "  {}: Option<{}>,\n",
+                    self.field_ident(field),
+                    self.get_field_type(field, context)?
+                )
+                .as_bytes(),
+            )?;
+        }
+        writer.write_all("}\n\n".as_bytes())?;
+
+        writer.write_all(format!("impl{} {}{} {{\n", generics, name, generics).as_bytes())?;
+        writer
+            .write_all(format!("  pub fn builder() -> {}{} {{\n", builder, generics).as_bytes())?;
+        writer.write_all(format!("    {} {{\n", builder).as_bytes())?;
+        for field in type_info.fields.iter() {
+            writer.write_all(format!("      {}: None,\n", self.field_ident(field)).as_bytes())?;
+        }
+        writer.write_all("    }\n  }\n}\n\n".as_bytes())?;
+
+        writer.write_all(format!("impl{} {}{} {{\n", generics, builder, generics).as_bytes())?;
+        for field in type_info.fields.iter() {
+            let ident = self.field_ident(field);
+            let base_type = self.get_field_base_type(field, context)?;
+            let wrapper = field
+                .config
+                .as_ref()
+                .and_then(|c| c.rust_type_wrapper.as_ref());
+            // optional fields are set to the value itself rather than an `Option`, as are `Cow`s
+            // of other types than strings, which don't convert from their owned values
+            let (value_type, value) = match (field.is_optional(), wrapper) {
+                (true, Some(wrapper)) => {
+                    (base_type, self.wrapped_value(wrapper, "Some(value.into())"))
+                }
+                (true, None) => (base_type, "Some(value.into())".to_owned()),
+                (false, Some(RustTypeWrapper::Cow)) if base_type != "String" => (
+                    base_type,
+                    self.wrapped_value(&RustTypeWrapper::Cow, "value.into()"),
+                ),
+                _ => (
+                    self.get_field_type(field, context)?,
+                    "value.into()".to_owned(),
+                ),
+            };
+            writer.write_all(
+                format!(
+                    "  pub fn {}(mut self, value: impl Into<{}>) -> Self {{\n",
+                    ident, value_type
+                )
+                .as_bytes(),
+            )?;
+            writer.write_all(
+                format!("    self.{} = Some({});\n    self\n  }}\n\n", ident, value).as_bytes(),
+            )?;
+        }
+        writer.write_all(
+            format!(
+                "  pub fn build(self) -> Result<{}{}, fluorite::BuildError> {{\n",
+                name, generics
+            )
+            .as_bytes(),
+        )?;
+        writer.write_all(format!("    Ok({} {{\n", name).as_bytes())?;
+        for field in type_info.fields.iter() {
+            let ident = self.field_ident(field);
+            let value = match (&field.default, field.is_optional()) {
+                (Some(_), _) => format!(
+                    "self.{}.unwrap_or_else({})",
+                    ident,
+                    self.default_fn_name(field, type_info, None)
+                ),
+                (None, true) => format!("self.{}.unwrap_or_default()", ident),
+                // the schema name of the field, which users know better than its identifier
+                (None, _) => format!(
+                    "self.{}.ok_or_else(|| fluorite::BuildError::new(\"{}\", {:?}))?",
+                    ident, name, field.name
+                ),
+            };
+            writer.write_all(format!("      {}: {},\n", ident, value).as_bytes())?;
+        }
+        writer.write_all("    })\n  }\n}\n\n".as_bytes())?;
+        Ok(())
+    }
+
+    /// Sanitized names may collide, e.g. `first-name` and `first_name`.
+    fn check_field_idents(&self, type_info: &ObjectTypeInfo) -> anyhow::Result<()> {
+        let mut idents = HashSet::new();
+        for field in type_info.fields.iter() {
+            let ident = self.field_ident(field);
+            if !idents.insert(serde_name(&ident).to_owned()) {
+                return Err(anyhow!(
+                    "Field {}.{} collides with another field as Rust identifier {}",
+                    type_info.name,
+                    field.name,
+                    ident
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Default functions of inline variants are named after their enums as well, since their
+    /// objects may be inlined in several enums and be standalone at the same time.
+    fn default_fn_name(
+        &self,
+        field: &ObjectField,
+        type_info: &ObjectTypeInfo,
+        variant_of: Option<&str>,
+    ) -> String {
+        let prefix = match variant_of {
+            Some(object_enum) => format!("{}_", to_snake_case(object_enum)),
+            None => "".to_owned(),
+        };
+        format!(
+            "default_{}{}_{}",
+            prefix,
+            to_snake_case(&type_info.name),
+            serde_name(&self.field_ident(field))
+        )
+    }
+
+    /// Writes the functions referenced by `#[serde(default = "...")]`, plus a `Default` impl
+    /// when every field of a standalone object has a default value.
+    fn write_default_values(
+        &self,
+        writer: &mut dyn Write,
+        type_info: &ObjectTypeInfo,
+        variant_of: Option<&str>,
+        context: &RustContext,
+    ) -> anyhow::Result<()> {
+        for field in type_info.fields.iter() {
+            if let Some(value) = &field.default {
+                let value = context.get_default_value(&field.field_type, value)?;
+                let value = match field.is_optional() {
+                    true => format!("Some({})", value),
+                    _ => value,
+                };
+                let value = match field
+                    .config
+                    .as_ref()
+                    .and_then(|c| c.rust_type_wrapper.as_ref())
+                {
+                    Some(wrapper) => self.wrapped_value(wrapper, &value),
+                    _ => value,
+                };
+                writer.write_all(
+                    format!(
+                        "fn {}{}() -> {} {{\n  {}\n}}\n\n",
+                        self.default_fn_name(field, type_info, variant_of),
+                        self.generics(&field.field_type.type_params()),
+                        self.get_field_type(field, context)?,
+                        value
+                    )
+                    .as_bytes(),
+                )?;
+            }
+        }
+
+        if variant_of.is_none() && type_info.fields.iter().all(|f| f.default.is_some()) {
+            let generics = self.generics(&type_info.type_params);
+            writer.write_all(
+                format!(
+                    "impl{} Default for {}{} {{\n",
+                    generics, type_info.name, generics
+                )
+                .as_bytes(),
+            )?;
+            writer.write_all("  fn default() -> Self {\n    Self {\n".as_bytes())?;
+            for field in type_info.fields.iter() {
+                writer.write_all(
+                    format!(
+                        "      {}: {}(),\n",
+                        self.field_ident(field),
+                        self.default_fn_name(field, type_info, variant_of)
+                    )
+                    .as_bytes(),
+                )?;
             }
+            writer.write_all("    }\n  }\n}\n\n".as_bytes())?;
         }
         Ok(())
     }
@@ -0,0 +1,175 @@
+use std::{collections::HashMap, fmt::Display, path::PathBuf};
+
+use yaml_rust2::{
+    parser::{Event, MarkedEventReceiver, Parser},
+    scanner::Marker,
+};
+
+use crate::definitions::Definition;
+
+/// A definition together with the file it was loaded from, if any.
+#[derive(Debug, Clone)]
+pub struct DefinitionFile {
+    pub path: Option<PathBuf>,
+    pub definition: Definition,
+    source_map: SourceMap,
+}
+
+impl DefinitionFile {
+    pub fn new(path: PathBuf, content: &str, definition: Definition) -> Self {
+        Self {
+            path: Some(path),
+            definition,
+            source_map: SourceMap::parse(content),
+        }
+    }
+
+    /// The location of the node at `path` (e.g. `types.2.fields.0.type`), or of its closest
+    /// ancestor found in the file.
+    pub fn locate(&self, path: &str) -> Option<Location> {
+        let mut path = path;
+        loop {
+            if let Some(location) = self.source_map.locations.get(path) {
+                return Some(*location);
+            }
+            path = path.rsplit_once('.')?.0;
+        }
+    }
+}
+
+impl From<Definition> for DefinitionFile {
+    fn from(definition: Definition) -> Self {
+        Self {
+            path: None,
+            definition,
+            source_map: SourceMap::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Locations of the nodes of a YAML document keyed by their dotted paths.
+#[derive(Debug, Clone, Default)]
+struct SourceMap {
+    locations: HashMap<String, Location>,
+}
+
+impl SourceMap {
+    fn parse(content: &str) -> SourceMap {
+        let mut builder = SourceMapBuilder::default();
+        // malformed documents are rejected by serde_yaml before getting here, so a partial
+        // source map is good enough
+        let _ = Parser::new(content.chars()).load(&mut builder, false);
+        builder.source_map
+    }
+}
+
+enum Node {
+    Mapping(Option<String>),
+    Sequence(usize),
+}
+
+#[derive(Default)]
+struct SourceMapBuilder {
+    nodes: Vec<Node>,
+    source_map: SourceMap,
+}
+
+impl SourceMapBuilder {
+    fn current_path(&self) -> String {
+        self.nodes
+            .iter()
+            .map(|n| match n {
+                Node::Mapping(key) => key.clone().unwrap_or_default(),
+                Node::Sequence(index) => index.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    fn start_value(&mut self, mark: Marker) {
+        let location = Location {
+            line: mark.line(),
+            column: mark.col() + 1,
+        };
+        self.source_map
+            .locations
+            .entry(self.current_path())
+            .or_insert(location);
+    }
+
+    fn end_value(&mut self) {
+        match self.nodes.last_mut() {
+            Some(Node::Mapping(key)) => *key = None,
+            Some(Node::Sequence(index)) => *index += 1,
+            None => {}
+        }
+    }
+}
+
+impl MarkedEventReceiver for SourceMapBuilder {
+    fn on_event(&mut self, ev: Event, mark: Marker) {
+        match ev {
+            Event::Scalar(value, ..) => match self.nodes.last_mut() {
+                Some(Node::Mapping(key @ None)) => *key = Some(value),
+                _ => {
+                    self.start_value(mark);
+                    self.end_value();
+                }
+            },
+            Event::MappingStart(..) => {
+                self.start_value(mark);
+                self.nodes.push(Node::Mapping(None));
+            }
+            Event::SequenceStart(..) => {
+                self.start_value(mark);
+                self.nodes.push(Node::Sequence(0));
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                self.nodes.pop();
+                self.end_value();
+            }
+            Event::Alias(_) => self.end_value(),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DefinitionFile, Location};
+
+    #[test]
+    fn test_locate_nodes() -> anyhow::Result<()> {
+        let content = r#"---
+configs:
+  rust_package: "tests"
+types:
+  - name: User
+    type: Object
+    fields:
+      - name: first_name
+        type: String
+      - { name: age, type: UInt32 }
+"#;
+        let file = DefinitionFile::new("test.yml".into(), content, serde_yaml::from_str(content)?);
+        let location = |line, column| Some(Location { line, column });
+        assert_eq!(file.locate("types.0.name"), location(5, 11));
+        assert_eq!(file.locate("types.0.fields.0.type"), location(9, 15));
+        assert_eq!(file.locate("types.0.fields.1.type"), location(10, 28));
+        assert_eq!(file.locate("types.0.fields.1.default"), location(10, 9));
+        assert_eq!(file.locate("configs.rust_package"), location(3, 17));
+        Ok(())
+    }
+}
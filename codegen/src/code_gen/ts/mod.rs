@@ -18,6 +18,7 @@ impl CodeGenContext for TsContext {
     }
 }
 pub struct TsProvider {
+    #[allow(dead_code)]
     options: TsOptions,
 }
 
@@ -1,13 +1,27 @@
 use core::fmt;
-use std::{collections::HashMap, fmt::Display, str::FromStr};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    str::FromStr,
+};
+
+use anyhow::anyhow;
+use fluorite::Any;
 
-use crate::definitions::{CustomType, Definition, ObjectEnumStyle::Extern, SimpleType};
+use crate::definitions::{
+    CustomType, Definition, EnumRepresentation, NamingConvention, ObjectEnumRepresentation,
+    ObjectEnumStyle::Extern, SimpleType, TypeConfig,
+};
 
 use super::abi::{
-    CodeGenContext, EnumTypeInfo, ListTypeInfo, MapTypeInfo, ObjectEnumTypeInfo, ObjectEnumValue,
-    ObjectField, ObjectTypeInfo, PreProcessor, TypeInfo, TypeName,
+    AliasTypeInfo, CodeGenContext, EnumTypeInfo, ListTypeInfo, MapTypeInfo, NewtypeTypeInfo,
+    ObjectEnumTagging, ObjectEnumTypeInfo, ObjectEnumValue, ObjectField, ObjectTypeInfo,
+    PreProcessor, TypeInfo, TypeName,
 };
 
+/// The key of the payload of variants in object enums without a `content_key`.
+pub(crate) const DEFAULT_CONTENT_KEY: &str = "content";
+
 impl Display for SimpleType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:?}", &self)
@@ -39,12 +53,22 @@ impl SimpleType {
 impl CustomType {
     pub(crate) fn type_name(&self) -> &str {
         match self {
-            CustomType::Object { name, fields: _ } => name.as_str(),
-            CustomType::Enum { name, values: _ } => name.as_str(),
+            CustomType::Object {
+                name,
+                type_params: _,
+                fields: _,
+                configs: _,
+            } => name.as_str(),
+            CustomType::Enum {
+                name,
+                values: _,
+                configs: _,
+            } => name.as_str(),
             CustomType::ObjectEnum {
                 name,
                 type_tag: _,
                 values: _,
+                variants: _,
                 configs: _,
             } => name.as_str(),
             CustomType::List { name, item_type: _ } => name.as_str(),
@@ -53,40 +77,136 @@ impl CustomType {
                 key_type: _,
                 value_type: _,
             } => name.as_str(),
+            CustomType::Alias {
+                name,
+                target_type: _,
+            } => name.as_str(),
+            CustomType::Newtype {
+                name,
+                target_type: _,
+            } => name.as_str(),
         }
     }
 }
 
-pub fn to_snake_case(s: &str) -> String {
-    let mut snake_case = String::new();
-    let mut chars = s.chars().peekable();
+impl Definition {
+    /// The package of this definition for a backend: its entry in `packages`, falling back to
+    /// the language-neutral `package`.
+    pub fn package_for(&self, language: &str) -> Option<&str> {
+        let configs = self.configs.as_ref()?;
+        configs
+            .packages
+            .as_ref()
+            .and_then(|p| p.get(language))
+            .or(configs.package.as_ref())
+            .map(String::as_str)
+    }
+}
 
-    while let Some(c) = chars.next() {
-        if c.is_uppercase() && !snake_case.is_empty() && chars.peek().is_some() {
-            snake_case.push('_');
+impl NamingConvention {
+    /// Converts a name to this naming convention, e.g. `HTTPServer` to `httpServer`.
+    pub fn apply(&self, name: &str) -> String {
+        match self {
+            NamingConvention::CamelCase => to_camel_case(name),
+            NamingConvention::SnakeCase => to_snake_case(name),
+            NamingConvention::ScreamingSnakeCase => to_snake_case(name).to_uppercase(),
+            NamingConvention::KebabCase => to_snake_case(name).replace('_', "-"),
         }
-        snake_case.extend(c.to_lowercase());
     }
+}
 
-    snake_case
+/// Splits a name into words at non-alphanumeric characters and case changes, keeping
+/// acronyms together, e.g. `HTTPServer` into `HTTP` and `Server`.
+pub fn split_words(s: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    for part in s.split(|c: char| !c.is_alphanumeric()) {
+        let chars = part.char_indices().collect::<Vec<_>>();
+        let mut start = 0;
+        for i in 1..chars.len() {
+            let (index, c) = chars[i];
+            let prev = chars[i - 1].1;
+            let next_is_lowercase = chars.get(i + 1).is_some_and(|(_, n)| n.is_lowercase());
+            let is_boundary = c.is_uppercase()
+                && (prev.is_lowercase()
+                    || prev.is_numeric()
+                    || (prev.is_uppercase() && next_is_lowercase));
+            if is_boundary {
+                words.push(&part[start..index]);
+                start = index;
+            }
+        }
+        if start < part.len() {
+            words.push(&part[start..]);
+        }
+    }
+    words
+}
+
+pub fn to_snake_case(s: &str) -> String {
+    split_words(s)
+        .iter()
+        .map(|w| w.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+pub fn to_pascal_case(s: &str) -> String {
+    split_words(s).iter().map(|w| capitalize(w)).collect()
+}
+
+pub fn to_camel_case(s: &str) -> String {
+    split_words(s)
+        .iter()
+        .enumerate()
+        .map(|(i, w)| match i {
+            0 => w.to_lowercase(),
+            _ => capitalize(w),
+        })
+        .collect()
 }
 
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.as_str().to_lowercase().chars())
+            .collect(),
+        None => "".to_owned(),
+    }
+}
+
+/// Builds the type dictionary keyed by fully qualified type names, e.g. `protocols.users.User`.
+/// All type references are resolved to fully qualified names as well.
 pub(crate) fn build_type_dict<C: CodeGenContext>(
-    definitions: &Vec<Definition>,
-    pre_processor: &Box<dyn PreProcessor<C>>,
+    definitions: &[&Definition],
+    pre_processor: &dyn PreProcessor<C>,
 ) -> anyhow::Result<HashMap<String, TypeInfo>> {
+    // packages of all types by their bare names
+    let mut type_packages: HashMap<String, Vec<String>> = HashMap::new();
+    for d in definitions {
+        let package = pre_processor.get_package_name(d)?;
+        for t in &d.types {
+            type_packages
+                .entry(t.type_name().to_owned())
+                .or_default()
+                .push(package.clone());
+        }
+    }
+
     let mut object_enum_value_type_names = Vec::new();
-    let mut all_type_names = Vec::new();
     for d in definitions {
+        let package = pre_processor.get_package_name(d)?;
         for t in &d.types {
-            all_type_names.push(t.type_name().to_owned());
             if let CustomType::ObjectEnum {
                 values, configs, ..
             } = t
             {
                 if configs.clone().and_then(|c| c.object_enum_style) != Some(Extern) {
-                    for v in values {
-                        object_enum_value_type_names.push(v.clone());
+                    for v in values.iter().flatten() {
+                        if let Some(v) = resolve_type_reference(v, &package, &type_packages)? {
+                            object_enum_value_type_names.push(v);
+                        }
                     }
                 }
             }
@@ -97,56 +217,129 @@ pub(crate) fn build_type_dict<C: CodeGenContext>(
     let mut all_types: HashMap<String, TypeInfo> = HashMap::new();
     for d in definitions {
         let package = pre_processor.get_package_name(d)?;
+        let resolve = |type_name: &str| -> anyhow::Result<TypeName> {
+            resolve_type_name(TypeName::from_str(type_name)?, &package, &type_packages)
+        };
+        // naming conventions of types fall back to the one of their definition
+        let rename_all = |configs: &Option<TypeConfig>| {
+            configs
+                .as_ref()
+                .and_then(|c| c.rename_all.clone())
+                .or_else(|| d.configs.as_ref().and_then(|c| c.rename_all.clone()))
+        };
         for t in &d.types {
+            let full_name = format!("{}.{}", package, t.type_name());
             match t {
-                CustomType::Object { name, fields } => {
-                    let fields = fields.iter().map(ObjectField::from).collect();
-                    let is_object_enum_value = object_enum_value_type_names.contains(name);
+                CustomType::Object {
+                    name,
+                    type_params,
+                    fields,
+                    configs,
+                } => {
+                    let type_params = type_params.clone().unwrap_or_default();
+                    let fields = fields
+                        .iter()
+                        .map(|f| {
+                            let mut field = ObjectField::try_from(f)?;
+                            field.field_type = resolve_type_name(
+                                field.field_type.with_type_params(&type_params),
+                                &package,
+                                &type_packages,
+                            )?;
+                            Ok(field)
+                        })
+                        .collect::<anyhow::Result<Vec<_>>>()?;
+                    let is_object_enum_value = object_enum_value_type_names.contains(&full_name);
                     let type_info = ObjectTypeInfo {
                         package: package.clone(),
                         name: name.clone(),
+                        type_params,
                         fields,
                         is_object_enum_value,
+                        is_standalone: !is_object_enum_value,
+                        rename_all: rename_all(configs),
+                        key_field: configs.as_ref().and_then(|c| c.key_field.clone()),
+                        configs: configs.clone(),
                     };
-                    all_types.insert(name.clone(), TypeInfo::Object(type_info));
+                    all_types.insert(full_name, TypeInfo::Object(type_info));
                 }
-                CustomType::Enum { name, values } => {
+                CustomType::Enum {
+                    name,
+                    values,
+                    configs,
+                } => {
+                    let representation = configs
+                        .as_ref()
+                        .and_then(|c| c.enum_representation.clone())
+                        .unwrap_or(EnumRepresentation::String);
+                    let explicit = configs.as_ref().and_then(|c| c.discriminants.as_ref());
+                    let discriminants = match (&representation, explicit) {
+                        (EnumRepresentation::String, None) => None,
+                        _ => Some(
+                            resolve_discriminants(values, explicit)
+                                .map_err(|e| anyhow!("Invalid enum {}: {}", name, e))?,
+                        ),
+                    };
                     let type_info = EnumTypeInfo {
                         package: package.clone(),
                         name: name.clone(),
                         values: values.clone(),
+                        rename_all: rename_all(configs),
+                        representation,
+                        discriminants,
+                        allow_unknown: configs
+                            .as_ref()
+                            .and_then(|c| c.allow_unknown)
+                            .unwrap_or(false),
+                        configs: configs.clone(),
                     };
-                    all_types.insert(name.clone(), TypeInfo::Enum(type_info));
+                    all_types.insert(full_name, TypeInfo::Enum(type_info));
                 }
                 CustomType::ObjectEnum {
                     name,
                     type_tag,
                     values,
+                    variants,
                     configs,
                 } => {
-                    let values = values
+                    let mut values = values
                         .iter()
-                        .map(|v| match all_type_names.contains(v) {
-                            true => ObjectEnumValue::CustomType(v.clone()),
-                            false => ObjectEnumValue::Simple(v.clone()),
+                        .flatten()
+                        .map(|v| {
+                            let value = match resolve_type_reference(v, &package, &type_packages)? {
+                                Some(v) => ObjectEnumValue::CustomType(v),
+                                None => ObjectEnumValue::Simple(v.clone()),
+                            };
+                            Ok(value)
                         })
-                        .collect();
+                        .collect::<anyhow::Result<Vec<_>>>()?;
+                    for variant in variants.iter().flatten() {
+                        let value = match &variant.payload_type {
+                            Some(payload_type) => ObjectEnumValue::Wrapped(
+                                variant.name.clone(),
+                                resolve(payload_type)?,
+                            ),
+                            None => ObjectEnumValue::Simple(variant.name.clone()),
+                        };
+                        values.push(value);
+                    }
                     let type_info = ObjectEnumTypeInfo {
                         package: package.clone(),
                         name: name.clone(),
-                        type_tag: type_tag.clone(),
+                        tagging: object_enum_tagging(name, type_tag.as_ref(), configs.as_ref())?,
                         values,
                         configs: configs.clone(),
+                        rename_all: rename_all(configs),
                     };
-                    all_types.insert(name.clone(), TypeInfo::ObjectEnum(type_info));
+                    all_types.insert(full_name, TypeInfo::ObjectEnum(type_info));
                 }
                 CustomType::List { name, item_type } => {
                     let type_info = ListTypeInfo {
                         package: package.clone(),
                         name: name.clone(),
-                        item_type: TypeName::from_str(item_type),
+                        item_type: resolve(item_type)?,
                     };
-                    all_types.insert(name.clone(), TypeInfo::List(type_info));
+                    all_types.insert(full_name, TypeInfo::List(type_info));
                 }
                 CustomType::Map {
                     name,
@@ -156,13 +349,652 @@ pub(crate) fn build_type_dict<C: CodeGenContext>(
                     let type_info = MapTypeInfo {
                         package: package.clone(),
                         name: name.clone(),
-                        key_type: TypeName::from_str(key_type),
-                        value_type: TypeName::from_str(value_type),
+                        key_type: resolve(key_type)?,
+                        value_type: resolve(value_type)?,
+                    };
+                    all_types.insert(full_name, TypeInfo::Map(type_info));
+                }
+                CustomType::Alias { name, target_type } => {
+                    let type_info = AliasTypeInfo {
+                        package: package.clone(),
+                        name: name.clone(),
+                        target_type: resolve(target_type)?,
+                    };
+                    all_types.insert(full_name, TypeInfo::Alias(type_info));
+                }
+                CustomType::Newtype { name, target_type } => {
+                    let type_info = NewtypeTypeInfo {
+                        package: package.clone(),
+                        name: name.clone(),
+                        target_type: resolve(target_type)?,
                     };
-                    all_types.insert(name.clone(), TypeInfo::Map(type_info));
+                    all_types.insert(full_name, TypeInfo::Newtype(type_info));
+                }
+            }
+        }
+    }
+    expand_refs(&mut all_types)?;
+    mark_standalone_objects(&mut all_types);
+    Ok(all_types)
+}
+
+/// Objects used as inline variants of object enums are standalone types as well when they are
+/// referenced anywhere else.
+fn mark_standalone_objects(type_dict: &mut HashMap<String, TypeInfo>) {
+    let mut referenced = HashSet::new();
+    for type_info in type_dict.values() {
+        match type_info {
+            TypeInfo::ObjectEnum(e) => {
+                let is_extern =
+                    e.configs.as_ref().and_then(|c| c.object_enum_style.clone()) == Some(Extern);
+                for value in e.values.iter() {
+                    match value {
+                        ObjectEnumValue::CustomType(t) if is_extern => {
+                            referenced.insert(t.clone());
+                        }
+                        ObjectEnumValue::Wrapped(_, t) => referenced.extend(t.custom_types()),
+                        _ => {}
+                    }
+                }
+            }
+            t => referenced.extend(t.get_referrenced_types()),
+        }
+    }
+    for (name, type_info) in type_dict.iter_mut() {
+        if let TypeInfo::Object(o) = type_info {
+            o.is_standalone = o.is_standalone || referenced.contains(name);
+        }
+    }
+}
+
+/// Generates a key type (e.g. `UserId`) for every object with a key field, used by the key
+/// field itself and in place of every `Ref<User>`.
+fn expand_refs(type_dict: &mut HashMap<String, TypeInfo>) -> anyhow::Result<()> {
+    let mut key_types = HashMap::new();
+    for (full_name, type_info) in type_dict.iter_mut() {
+        let TypeInfo::Object(object) = type_info else {
+            continue;
+        };
+        let Some(key_field) = &object.key_field else {
+            continue;
+        };
+        let field = object
+            .fields
+            .iter_mut()
+            .find(|f| &f.name == key_field)
+            .ok_or_else(|| anyhow!("Cannot find key field {}.{}", object.name, key_field))?;
+        let key_type = NewtypeTypeInfo {
+            package: object.package.clone(),
+            name: format!("{}Id", object.name),
+            target_type: field.field_type.clone(),
+        };
+        let key_type_name = format!("{}.{}", key_type.package, key_type.name);
+        field.field_type = TypeName::CustomType(key_type_name.clone());
+        key_types.insert(full_name.clone(), (key_type_name, key_type));
+    }
+
+    let expand = |type_name: &TypeName| -> anyhow::Result<TypeName> {
+        let mut error = None;
+        let expanded = type_name.map_leaves(&mut |t| match t {
+            TypeName::Ref(target) => match target.as_ref() {
+                TypeName::CustomType(name) if key_types.contains_key(name) => {
+                    Some(TypeName::CustomType(key_types[name].0.clone()))
+                }
+                _ => {
+                    error.get_or_insert(anyhow!("{} does not refer to an object with a key", t));
+                    None
+                }
+            },
+            _ => None,
+        });
+        match error {
+            Some(e) => Err(e),
+            None => Ok(expanded),
+        }
+    };
+    for type_info in type_dict.values_mut() {
+        match type_info {
+            TypeInfo::Object(o) => {
+                for field in o.fields.iter_mut() {
+                    field.field_type = expand(&field.field_type)?;
+                }
+            }
+            TypeInfo::List(l) => l.item_type = expand(&l.item_type)?,
+            TypeInfo::Map(m) => {
+                m.key_type = expand(&m.key_type)?;
+                m.value_type = expand(&m.value_type)?;
+            }
+            TypeInfo::Alias(a) => a.target_type = expand(&a.target_type)?,
+            TypeInfo::Newtype(n) => n.target_type = expand(&n.target_type)?,
+            TypeInfo::ObjectEnum(e) => {
+                for value in e.values.iter_mut() {
+                    if let ObjectEnumValue::Wrapped(_, payload_type) = value {
+                        *payload_type = expand(payload_type)?;
+                    }
                 }
             }
+            TypeInfo::Enum(_) => {}
+        }
+    }
+
+    for (key_type_name, key_type) in key_types.into_values() {
+        if type_dict.contains_key(&key_type_name) {
+            return Err(anyhow!("Key type {} already exists", key_type_name));
+        }
+        type_dict.insert(key_type_name, TypeInfo::Newtype(key_type));
+    }
+    Ok(())
+}
+
+/// Resolves all custom types referenced from `package` to their fully qualified names.
+/// References to unknown types are kept as is.
+fn resolve_type_name(
+    type_name: TypeName,
+    package: &str,
+    type_packages: &HashMap<String, Vec<String>>,
+) -> anyhow::Result<TypeName> {
+    let mut error = None;
+    let resolved = type_name.map_leaves(&mut |t| match t {
+        TypeName::CustomType(name) => match resolve_type_reference(name, package, type_packages) {
+            Ok(resolved) => resolved.map(TypeName::CustomType),
+            Err(e) => {
+                error.get_or_insert(e);
+                None
+            }
+        },
+        _ => None,
+    });
+    match error {
+        Some(e) => Err(e),
+        None => Ok(resolved),
+    }
+}
+
+/// Resolves a type reference from `package` to the fully qualified name of a known type.
+/// Qualified references (e.g. `protocols.users.User`) must match exactly, while bare ones are
+/// looked up in `package` first and then in all other packages, where they must be unique.
+pub(crate) fn resolve_type_reference(
+    name: &str,
+    package: &str,
+    type_packages: &HashMap<String, Vec<String>>,
+) -> anyhow::Result<Option<String>> {
+    if let Some((qualifier, type_name)) = name.rsplit_once('.') {
+        let found = type_packages
+            .get(type_name)
+            .is_some_and(|packages| packages.iter().any(|p| p == qualifier));
+        return Ok(found.then(|| name.to_owned()));
+    }
+    let packages = match type_packages.get(name) {
+        Some(packages) => packages,
+        None => return Ok(None),
+    };
+    if packages.iter().any(|p| p == package) {
+        return Ok(Some(format!("{}.{}", package, name)));
+    }
+    match packages.as_slice() {
+        [p] => Ok(Some(format!("{}.{}", p, name))),
+        _ => {
+            let candidates = packages
+                .iter()
+                .map(|p| format!("{}.{}", p, name))
+                .collect::<Vec<_>>();
+            Err(anyhow!(
+                "Ambiguous type reference {} in package {}, candidates: {}",
+                name,
+                package,
+                candidates.join(", ")
+            ))
+        }
+    }
+}
+
+fn object_enum_tagging(
+    name: &str,
+    type_tag: Option<&String>,
+    configs: Option<&TypeConfig>,
+) -> anyhow::Result<ObjectEnumTagging> {
+    let representation = configs
+        .and_then(|c| c.object_enum_representation.clone())
+        .unwrap_or(ObjectEnumRepresentation::Internal);
+    let tag = || {
+        type_tag
+            .cloned()
+            .ok_or_else(|| anyhow!("Missing type_tag for object enum {}", name))
+    };
+    let content = configs
+        .and_then(|c| c.content_key.clone())
+        .unwrap_or_else(|| DEFAULT_CONTENT_KEY.to_owned());
+    let tagging = match representation {
+        ObjectEnumRepresentation::Internal => ObjectEnumTagging::Internal {
+            tag: tag()?,
+            content,
+        },
+        ObjectEnumRepresentation::Adjacent => ObjectEnumTagging::Adjacent {
+            tag: tag()?,
+            content,
+        },
+        ObjectEnumRepresentation::External => ObjectEnumTagging::External,
+        ObjectEnumRepresentation::Untagged => ObjectEnumTagging::Untagged,
+    };
+    Ok(tagging)
+}
+
+/// The discriminant of every enum value, where values without an explicit one follow the
+/// previous value like in Rust and C, starting from 0.
+pub(crate) fn resolve_discriminants(
+    values: &[String],
+    explicit: Option<&HashMap<String, i32>>,
+) -> Result<Vec<i32>, String> {
+    let mut discriminants: Vec<i32> = vec![];
+    for value in values.iter() {
+        let discriminant = match (explicit.and_then(|e| e.get(value)), discriminants.last()) {
+            (Some(discriminant), _) => *discriminant,
+            (None, None) => 0,
+            (None, Some(previous)) => previous
+                .checked_add(1)
+                .ok_or_else(|| format!("discriminant of {} overflows Int32", value))?,
+        };
+        if let Some(index) = discriminants.iter().position(|d| *d == discriminant) {
+            return Err(format!(
+                "{} and {} have the same discriminant {}",
+                values[index], value, discriminant
+            ));
+        }
+        discriminants.push(discriminant);
+    }
+    Ok(discriminants)
+}
+
+pub(crate) fn any_to_i128(value: &Any) -> Option<i128> {
+    match value {
+        Any::UInt32(v) => Some(*v as i128),
+        Any::UInt64(v) => Some(*v as i128),
+        Any::Int32(v) => Some(*v as i128),
+        Any::Int64(v) => Some(*v as i128),
+        _ => None,
+    }
+}
+
+pub(crate) fn any_to_f64(value: &Any) -> Option<f64> {
+    match value {
+        Any::Float32(v) => Some(*v as f64),
+        Any::Float64(v) => Some(*v),
+        _ => any_to_i128(value).map(|v| v as f64),
+    }
+}
+
+/// Replaces every instantiation of a generic object (e.g. `Page<Order>`) with a concrete object
+/// (`PageOrder`) and drops the generic objects themselves, for backends without generics.
+pub(crate) fn monomorphize(
+    type_dict: HashMap<String, TypeInfo>,
+) -> anyhow::Result<HashMap<String, TypeInfo>> {
+    let (generic_types, types): (HashMap<_, _>, HashMap<_, _>) = type_dict
+        .into_iter()
+        .partition(|(_, t)| matches!(t, TypeInfo::Object(o) if !o.type_params.is_empty()));
+    let mut monomorphizer = Monomorphizer {
+        generic_types,
+        instances: HashMap::new(),
+    };
+    let mut all_types = HashMap::new();
+    for (name, type_info) in types {
+        let type_info = match type_info {
+            TypeInfo::Object(mut o) => {
+                for field in o.fields.iter_mut() {
+                    field.field_type = monomorphizer.rewrite(&field.field_type)?;
+                }
+                TypeInfo::Object(o)
+            }
+            TypeInfo::List(mut l) => {
+                l.item_type = monomorphizer.rewrite(&l.item_type)?;
+                TypeInfo::List(l)
+            }
+            TypeInfo::Map(mut m) => {
+                m.key_type = monomorphizer.rewrite(&m.key_type)?;
+                m.value_type = monomorphizer.rewrite(&m.value_type)?;
+                TypeInfo::Map(m)
+            }
+            TypeInfo::Alias(mut a) => {
+                a.target_type = monomorphizer.rewrite(&a.target_type)?;
+                TypeInfo::Alias(a)
+            }
+            TypeInfo::Newtype(mut n) => {
+                n.target_type = monomorphizer.rewrite(&n.target_type)?;
+                TypeInfo::Newtype(n)
+            }
+            TypeInfo::ObjectEnum(mut e) => {
+                for value in e.values.iter_mut() {
+                    if let ObjectEnumValue::Wrapped(_, payload_type) = value {
+                        *payload_type = monomorphizer.rewrite(payload_type)?;
+                    }
+                }
+                TypeInfo::ObjectEnum(e)
+            }
+            t => t,
+        };
+        all_types.insert(name, type_info);
+    }
+    for (name, (instantiation, instance)) in monomorphizer.instances {
+        if all_types.contains_key(&name) {
+            return Err(anyhow!(
+                "{} would be generated for {}, but a type with that name already exists",
+                name,
+                instantiation
+            ));
+        }
+        if let Some(instance) = instance {
+            all_types.insert(name, TypeInfo::Object(instance));
         }
     }
     Ok(all_types)
 }
+
+struct Monomorphizer {
+    generic_types: HashMap<String, TypeInfo>,
+    // keyed by the instance name, with the instantiation it was generated for; the instance is
+    // `None` while it is being built, so recursive instantiations terminate
+    instances: HashMap<String, (TypeName, Option<ObjectTypeInfo>)>,
+}
+
+impl Monomorphizer {
+    fn rewrite(&mut self, type_name: &TypeName) -> anyhow::Result<TypeName> {
+        let rewritten = match type_name {
+            TypeName::Generic(name, args) => {
+                let args = args
+                    .iter()
+                    .map(|a| self.rewrite(a))
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                TypeName::CustomType(self.instantiate(name, args)?)
+            }
+            TypeName::List(item) => TypeName::List(Box::new(self.rewrite(item)?)),
+            TypeName::Set(item) => TypeName::Set(Box::new(self.rewrite(item)?)),
+            TypeName::Optional(item) => TypeName::Optional(Box::new(self.rewrite(item)?)),
+            TypeName::Map(key, value) => {
+                TypeName::Map(Box::new(self.rewrite(key)?), Box::new(self.rewrite(value)?))
+            }
+            t => t.clone(),
+        };
+        Ok(rewritten)
+    }
+
+    fn instantiate(&mut self, name: &str, args: Vec<TypeName>) -> anyhow::Result<String> {
+        let generic_type = match self.generic_types.get(name) {
+            Some(TypeInfo::Object(o)) => o.clone(),
+            _ => return Err(anyhow!("Cannot find generic type: {}", name)),
+        };
+        if generic_type.type_params.len() != args.len() {
+            return Err(anyhow!(
+                "{} expects {} type argument(s) but got {}",
+                name,
+                generic_type.type_params.len(),
+                args.len()
+            ));
+        }
+        let package = &generic_type.package;
+        let instance_name = format!(
+            "{}{}",
+            generic_type.name,
+            args.iter()
+                .map(|a| mangle_type_name(a, package))
+                .collect::<String>()
+        );
+        let full_name = format!("{}.{}", package, instance_name);
+        let instantiation = TypeName::Generic(name.to_owned(), args.clone());
+        match self.instances.get(&full_name) {
+            Some((existing, _)) if *existing == instantiation => return Ok(full_name),
+            Some((existing, _)) => {
+                return Err(anyhow!(
+                    "{} and {} would both be generated as {}",
+                    existing,
+                    instantiation,
+                    full_name
+                ))
+            }
+            None => {}
+        }
+        self.instances
+            .insert(full_name.clone(), (instantiation.clone(), None));
+
+        let bindings: HashMap<String, TypeName> =
+            generic_type.type_params.iter().cloned().zip(args).collect();
+        let mut instance = generic_type;
+        instance.name = instance_name;
+        instance.type_params = vec![];
+        for field in instance.fields.iter_mut() {
+            field.field_type = self.rewrite(&field.field_type.substitute(&bindings))?;
+        }
+        self.instances
+            .insert(full_name.clone(), (instantiation, Some(instance)));
+        Ok(full_name)
+    }
+}
+
+/// Names a type argument for an instance of a generic type defined in `package`. Types from other
+/// packages are prefixed with their package path, so `Page<billing.Status>` and
+/// `Page<shipping.Status>` become `PageBillingStatus` and `PageShippingStatus`.
+fn mangle_type_name(type_name: &TypeName, package: &str) -> String {
+    let mangle_name = |name: &str| match name.rsplit_once('.') {
+        Some((p, name)) if p == package => name.to_owned(),
+        Some((p, name)) => format!(
+            "{}{}",
+            p.split('.').map(to_pascal_case).collect::<String>(),
+            name
+        ),
+        None => name.to_owned(),
+    };
+    match type_name {
+        TypeName::CustomType(name) | TypeName::TypeParam(name) => mangle_name(name),
+        TypeName::Generic(name, args) => format!(
+            "{}{}",
+            mangle_name(name),
+            args.iter()
+                .map(|a| mangle_type_name(a, package))
+                .collect::<String>()
+        ),
+        TypeName::List(item) => format!("List{}", mangle_type_name(item, package)),
+        TypeName::Set(item) => format!("Set{}", mangle_type_name(item, package)),
+        TypeName::Optional(item) => format!("Optional{}", mangle_type_name(item, package)),
+        TypeName::Map(key, value) => format!(
+            "Map{}{}",
+            mangle_type_name(key, package),
+            mangle_type_name(value, package)
+        ),
+        t => t.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{build_type_dict, monomorphize, split_words, to_snake_case};
+    use crate::{
+        code_gen::{
+            abi::{TypeInfo, TypeName},
+            rust::{RustOptions, RustPreProcessor},
+        },
+        definitions::{Definition, NamingConvention},
+    };
+
+    #[test]
+    fn test_naming_conventions() {
+        assert_eq!(split_words("HTTPServer"), vec!["HTTP", "Server"]);
+        assert_eq!(split_words("first_name"), vec!["first", "name"]);
+        assert_eq!(split_words("UInt32"), vec!["U", "Int32"]);
+        assert_eq!(to_snake_case("UserA"), "user_a");
+        assert_eq!(to_snake_case("ObjectEnum"), "object_enum");
+        assert_eq!(
+            NamingConvention::CamelCase.apply("HTTPServer"),
+            "httpServer"
+        );
+        assert_eq!(
+            NamingConvention::CamelCase.apply("display_name"),
+            "displayName"
+        );
+        assert_eq!(
+            NamingConvention::ScreamingSnakeCase.apply("ProTeam"),
+            "PRO_TEAM"
+        );
+        assert_eq!(NamingConvention::KebabCase.apply("2fa_code"), "2fa-code");
+    }
+
+    #[test]
+    fn test_monomorphize() -> anyhow::Result<()> {
+        let definition = serde_yaml::from_str(
+            r#"
+configs:
+  rust_package: pages
+types:
+  - name: Page
+    type: Object
+    type_params: [T]
+    fields:
+      - name: items
+        type: List<T>
+      - name: next
+        type: Optional<Page<T>>
+  - name: Order
+    type: Object
+    fields:
+      - name: id
+        type: String
+  - name: Orders
+    type: Object
+    fields:
+      - name: page
+        type: Page<Order>
+"#,
+        )?;
+        let pre_processor = RustPreProcessor {
+            options: RustOptions::new("".to_owned()),
+        };
+        let type_dict = monomorphize(build_type_dict(&[&definition], &pre_processor)?)?;
+
+        assert!(!type_dict.contains_key("pages.Page"));
+        let Some(TypeInfo::Object(page)) = type_dict.get("pages.PageOrder") else {
+            panic!("PageOrder is not generated");
+        };
+        assert_eq!(page.name, "PageOrder");
+        assert_eq!(
+            page.fields[0].field_type,
+            TypeName::List(Box::new(TypeName::CustomType("pages.Order".to_owned())))
+        );
+        assert_eq!(
+            page.fields[1].field_type,
+            TypeName::Optional(Box::new(TypeName::CustomType("pages.PageOrder".to_owned())))
+        );
+        let Some(TypeInfo::Object(orders)) = type_dict.get("pages.Orders") else {
+            panic!("Orders is not generated");
+        };
+        assert_eq!(
+            orders.fields[0].field_type,
+            TypeName::CustomType("pages.PageOrder".to_owned())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_monomorphize_across_packages() -> anyhow::Result<()> {
+        let definition = |package: &str, types: &str| {
+            serde_yaml::from_str::<Definition>(&format!(
+                "configs:\n  rust_package: {}\ntypes:\n{}",
+                package, types
+            ))
+        };
+        let status = "  - name: Status\n    type: Enum\n    values: [Open, Closed]\n";
+        let billing = definition("billing", status)?;
+        let shipping = definition("shipping", status)?;
+        let pages = definition(
+            "pages",
+            r#"  - name: Page
+    type: Object
+    type_params: [T]
+    fields:
+      - name: items
+        type: List<T>
+  - name: Statuses
+    type: Object
+    fields:
+      - name: billing
+        type: Page<billing.Status>
+      - name: shipping
+        type: Page<shipping.Status>
+"#,
+        )?;
+        let pre_processor = RustPreProcessor {
+            options: RustOptions::new("".to_owned()),
+        };
+        let definitions = [&billing, &shipping, &pages];
+        let type_dict = monomorphize(build_type_dict(&definitions, &pre_processor)?)?;
+
+        let Some(TypeInfo::Object(statuses)) = type_dict.get("pages.Statuses") else {
+            panic!("Statuses is not generated");
+        };
+        assert_eq!(
+            statuses.fields[0].field_type,
+            TypeName::CustomType("pages.PageBillingStatus".to_owned())
+        );
+        assert_eq!(
+            statuses.fields[1].field_type,
+            TypeName::CustomType("pages.PageShippingStatus".to_owned())
+        );
+        let Some(TypeInfo::Object(page)) = type_dict.get("pages.PageShippingStatus") else {
+            panic!("PageShippingStatus is not generated");
+        };
+        assert_eq!(
+            page.fields[0].field_type,
+            TypeName::List(Box::new(TypeName::CustomType("shipping.Status".to_owned())))
+        );
+
+        let clashing = definition(
+            "pages",
+            "  - name: PageBillingStatus\n    type: Object\n    fields: []\n",
+        )?;
+        let definitions = [&billing, &shipping, &pages, &clashing];
+        let Err(err) = monomorphize(build_type_dict(&definitions, &pre_processor)?) else {
+            panic!("PageBillingStatus clashes with the instance of Page<billing.Status>");
+        };
+        assert_eq!(
+            err.to_string(),
+            "pages.PageBillingStatus would be generated for pages.Page<billing.Status>, \
+             but a type with that name already exists"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_standalone_objects() -> anyhow::Result<()> {
+        let definition = serde_yaml::from_str(
+            r#"
+configs:
+  rust_package: events
+types:
+  - name: Event
+    type: ObjectEnum
+    type_tag: type
+    values: [Created, Deleted]
+  - name: Created
+    type: Object
+    fields:
+      - name: id
+        type: String
+  - name: Deleted
+    type: Object
+    fields:
+      - name: id
+        type: String
+  - name: Audit
+    type: Object
+    fields:
+      - name: created
+        type: List<Created>
+"#,
+        )?;
+        let pre_processor = RustPreProcessor {
+            options: RustOptions::new("".to_owned()),
+        };
+        let type_dict = build_type_dict(&[&definition], &pre_processor)?;
+
+        assert!(type_dict["events.Created"].is_standalone());
+        assert!(!type_dict["events.Deleted"].is_standalone());
+        assert!(type_dict["events.Audit"].is_standalone());
+        Ok(())
+    }
+}
@@ -0,0 +1,889 @@
+use std::{collections::HashMap, fmt::Display, path::PathBuf, str::FromStr};
+
+use fluorite::Any;
+
+use super::{
+    abi::TypeName,
+    source::{DefinitionFile, Location},
+    utils::{
+        any_to_f64, any_to_i128, resolve_discriminants, resolve_type_reference, to_pascal_case,
+        DEFAULT_CONTENT_KEY,
+    },
+};
+use crate::definitions::{
+    CustomType, Definition, EnumRepresentation, ObjectEnumRepresentation, SimpleType,
+};
+
+/// A problem found in a definition file.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub file: Option<PathBuf>,
+    pub location: Option<Location>,
+    pub message: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+            if let Some(location) = &self.location {
+                write!(f, "{}:", location)?;
+            }
+            write!(f, " ")?;
+        }
+        write!(f, "error: {}", self.message)
+    }
+}
+
+/// All errors found by the validation of definition files.
+#[derive(Debug)]
+pub struct ValidationError {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Found {} problem(s) in definitions:",
+            self.diagnostics.len()
+        )?;
+        for diagnostic in self.diagnostics.iter() {
+            write!(f, "\n  {}", diagnostic)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Checks all definitions for problems before any code is generated, returning all errors at
+/// once.
+pub(crate) fn validate_definitions(
+    files: &[DefinitionFile],
+    get_package_name: &dyn Fn(&Definition) -> anyhow::Result<String>,
+) -> Result<(), ValidationError> {
+    let mut validator = Validator {
+        files,
+        packages: vec![],
+        types: HashMap::new(),
+        type_packages: HashMap::new(),
+        diagnostics: vec![],
+    };
+    validator.validate(get_package_name);
+
+    let mut diagnostics = validator.diagnostics;
+    match diagnostics.is_empty() {
+        true => Ok(()),
+        _ => {
+            diagnostics.sort_by_key(|d| (d.file.clone(), d.location.map(|l| (l.line, l.column))));
+            Err(ValidationError { diagnostics })
+        }
+    }
+}
+
+struct Validator<'a> {
+    files: &'a [DefinitionFile],
+    packages: Vec<Option<String>>,
+    // all types by their fully qualified names, along with where they are defined
+    types: HashMap<String, (usize, String, &'a CustomType)>,
+    type_packages: HashMap<String, Vec<String>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Validator<'a> {
+    fn validate(&mut self, get_package_name: &dyn Fn(&Definition) -> anyhow::Result<String>) {
+        let files = self.files;
+        for (file, definition_file) in files.iter().enumerate() {
+            let package = match get_package_name(&definition_file.definition) {
+                Ok(package) => Some(package),
+                Err(e) => {
+                    self.error(file, "configs", e.to_string());
+                    None
+                }
+            };
+            self.packages.push(package);
+        }
+
+        for (file, definition_file) in files.iter().enumerate() {
+            let Some(package) = self.packages[file].clone() else {
+                continue;
+            };
+            for (index, t) in definition_file.definition.types.iter().enumerate() {
+                let path = format!("types.{}", index);
+                let name = t.type_name();
+                self.check_identifier(file, &format!("{}.name", path), "type", name);
+                let full_name = format!("{}.{}", package, name);
+                match self.types.get(&full_name) {
+                    Some((other_file, other_path, _)) => {
+                        let first_path = format!("{}.name", other_path);
+                        let first = self.describe_location(*other_file, &first_path);
+                        self.error(
+                            file,
+                            &format!("{}.name", path),
+                            format!("duplicate type {}, first defined at {}", full_name, first),
+                        );
+                    }
+                    None => {
+                        self.types.insert(full_name, (file, path, t));
+                        self.type_packages
+                            .entry(name.to_owned())
+                            .or_default()
+                            .push(package.clone());
+                    }
+                }
+            }
+        }
+
+        for (file, definition_file) in files.iter().enumerate() {
+            let Some(package) = self.packages[file].clone() else {
+                continue;
+            };
+            for (index, t) in definition_file.definition.types.iter().enumerate() {
+                self.check_type(file, &package, &format!("types.{}", index), t);
+            }
+        }
+    }
+
+    fn check_type(&mut self, file: usize, package: &str, path: &str, t: &'a CustomType) {
+        match t {
+            CustomType::Object {
+                name,
+                type_params,
+                fields,
+                configs,
+            } => {
+                let type_params = type_params.clone().unwrap_or_default();
+                for (index, param) in type_params.iter().enumerate() {
+                    let param_path = format!("{}.type_params.{}", path, index);
+                    self.check_identifier(file, &param_path, "type parameter", param);
+                    if type_params[..index].contains(param) {
+                        let message = format!("duplicate type parameter {} in {}", param, name);
+                        self.error(file, &param_path, message);
+                    }
+                }
+                for (index, field) in fields.iter().enumerate() {
+                    let field_path = format!("{}.fields.{}", path, index);
+                    self.check_name(file, &format!("{}.name", field_path), "field", &field.name);
+                    if fields[..index].iter().any(|f| f.name == field.name) {
+                        let message = format!("duplicate field {} in {}", field.name, name);
+                        self.error(file, &format!("{}.name", field_path), message);
+                    }
+                    let type_path = format!("{}.type", field_path);
+                    let field_type = self.check_type_expression(
+                        file,
+                        package,
+                        &type_path,
+                        &field.field_type,
+                        &type_params,
+                    );
+                    let item_wrapper = field
+                        .configs
+                        .as_ref()
+                        .and_then(|c| c.rust_item_wrapper.as_ref());
+                    if let (Some(field_type), Some(_)) = (&field_type, item_wrapper) {
+                        if !matches!(
+                            field_type,
+                            TypeName::List(_) | TypeName::Set(_) | TypeName::Map(_, _)
+                        ) {
+                            let message = format!(
+                                "rust_item_wrapper of field {}.{} requires a List, Set or Map, found {}",
+                                name, field.name, field.field_type
+                            );
+                            let wrapper_path = format!("{}.configs.rust_item_wrapper", field_path);
+                            self.error(file, &wrapper_path, message);
+                        }
+                    }
+                    let rust_type = field.configs.as_ref().and_then(|c| c.rust_type.as_ref());
+                    if let Some(rust_type) = rust_type {
+                        let rust_type_path = format!("{}.configs.rust_type", field_path);
+                        if rust_type.trim().is_empty() {
+                            let message = format!(
+                                "rust_type of field {}.{} must not be empty",
+                                name, field.name
+                            );
+                            self.error(file, &rust_type_path, message);
+                        } else if item_wrapper.is_some() {
+                            let message = format!(
+                                "field {}.{} cannot have both rust_type and rust_item_wrapper",
+                                name, field.name
+                            );
+                            self.error(file, &rust_type_path, message);
+                        } else if field.default.is_some() {
+                            let message = format!(
+                                "field {}.{} cannot have a default value with a rust_type",
+                                name, field.name
+                            );
+                            self.error(file, &rust_type_path, message);
+                        }
+                    }
+                    if let (Some(field_type), Some(default)) = (field_type, &field.default) {
+                        if let Err(message) = self.check_default_value(&field_type, default) {
+                            let message = format!(
+                                "invalid default value for field {}.{}: {}",
+                                name, field.name, message
+                            );
+                            self.error(file, &format!("{}.default", field_path), message);
+                        }
+                    }
+                }
+                if let Some(key_field) = configs.as_ref().and_then(|c| c.key_field.as_ref()) {
+                    let key_path = format!("{}.configs.key_field", path);
+                    self.check_key_field(file, package, &key_path, t, key_field);
+                }
+            }
+            CustomType::Enum {
+                name,
+                values,
+                configs,
+            } => {
+                for (index, value) in values.iter().enumerate() {
+                    let value_path = format!("{}.values.{}", path, index);
+                    self.check_name(file, &value_path, "enum value", value);
+                    if values[..index].contains(value) {
+                        let message = format!("duplicate enum value {} in {}", value, name);
+                        self.error(file, &value_path, message);
+                    }
+                }
+                let idents = values
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| {
+                        let value_path = format!("{}.values.{}", path, index);
+                        (value_path, value.as_str(), to_pascal_case(value))
+                    })
+                    .collect::<Vec<_>>();
+                self.check_variant_idents(file, name, &idents);
+                if let Some(discriminants) = configs.as_ref().and_then(|c| c.discriminants.as_ref())
+                {
+                    let discriminants_path = format!("{}.configs.discriminants", path);
+                    let mut unknown_values = discriminants
+                        .keys()
+                        .filter(|v| !values.contains(v))
+                        .collect::<Vec<_>>();
+                    unknown_values.sort();
+                    for value in unknown_values {
+                        let message = format!("discriminant for unknown value {}.{}", name, value);
+                        self.error(file, &discriminants_path, message);
+                    }
+                    if let Err(e) = resolve_discriminants(values, Some(discriminants)) {
+                        let message = format!("invalid discriminants of {}: {}", name, e);
+                        self.error(file, &discriminants_path, message);
+                    }
+                }
+                if let Some(configs) = configs.as_ref().filter(|c| c.allow_unknown == Some(true)) {
+                    if configs.discriminants.is_some()
+                        || configs.enum_representation == Some(EnumRepresentation::Int)
+                    {
+                        let message = format!(
+                            "{} cannot allow unknown values as it has discriminants",
+                            name
+                        );
+                        self.error(file, &format!("{}.configs.allow_unknown", path), message);
+                    }
+                    let names = values
+                        .iter()
+                        .enumerate()
+                        .map(|(index, value)| {
+                            (format!("{}.values.{}", path, index), value.as_str())
+                        })
+                        .collect::<Vec<_>>();
+                    self.check_unknown_variant(file, name, &names);
+                }
+            }
+            CustomType::ObjectEnum {
+                name,
+                type_tag,
+                values,
+                variants,
+                configs,
+            } => {
+                let values = values.as_deref().unwrap_or_default();
+                let variants = variants.as_deref().unwrap_or_default();
+                // names of all variants, along with where they are defined
+                let names = values
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| (format!("{}.values.{}", path, index), value.as_str()))
+                    .chain(variants.iter().enumerate().map(|(index, variant)| {
+                        let variant_path = format!("{}.variants.{}.name", path, index);
+                        (variant_path, variant.name.as_str())
+                    }))
+                    .collect::<Vec<_>>();
+                if configs.as_ref().and_then(|c| c.allow_unknown) == Some(true) {
+                    self.check_unknown_variant(file, name, &names);
+                }
+                self.check_variant_tags(file, path, t, &names);
+                let representation = configs
+                    .as_ref()
+                    .and_then(|c| c.object_enum_representation.clone())
+                    .unwrap_or(ObjectEnumRepresentation::Internal);
+                // only internally tagged variants share their objects with the tag
+                let mut internal_tag = None;
+                match (&representation, type_tag) {
+                    (
+                        ObjectEnumRepresentation::Internal | ObjectEnumRepresentation::Adjacent,
+                        Some(type_tag),
+                    ) => {
+                        if representation == ObjectEnumRepresentation::Internal {
+                            internal_tag = Some(type_tag);
+                        }
+                        let content_key = configs
+                            .as_ref()
+                            .and_then(|c| c.content_key.as_deref())
+                            .unwrap_or(DEFAULT_CONTENT_KEY);
+                        if type_tag == content_key {
+                            let message = format!(
+                                "type_tag {} of {} is also its content_key",
+                                type_tag, name
+                            );
+                            self.error(file, &format!("{}.type_tag", path), message);
+                        }
+                    }
+                    (
+                        ObjectEnumRepresentation::Internal | ObjectEnumRepresentation::Adjacent,
+                        _,
+                    ) => {
+                        let message = format!(
+                            "type_tag is required by internally or adjacently tagged {}",
+                            name
+                        );
+                        self.error(file, &format!("{}.name", path), message);
+                    }
+                    _ => {}
+                }
+                for (index, (variant_path, variant)) in names.iter().enumerate() {
+                    if names[..index].iter().any(|(_, n)| n == variant) {
+                        let message = format!("duplicate enum value {} in {}", variant, name);
+                        self.error(file, variant_path, message);
+                    }
+                }
+                // values are named after their types, variants are turned into identifiers
+                let idents = names
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (variant_path, variant))| {
+                        let ident = match index < values.len() {
+                            true => variant.rsplit('.').next().unwrap_or(variant).to_owned(),
+                            _ => to_pascal_case(variant),
+                        };
+                        (variant_path.clone(), *variant, ident)
+                    })
+                    .collect::<Vec<_>>();
+                self.check_variant_idents(file, name, &idents);
+                for (index, variant) in variants.iter().enumerate() {
+                    let variant_path = format!("{}.variants.{}", path, index);
+                    self.check_name(
+                        file,
+                        &format!("{}.name", variant_path),
+                        "variant",
+                        &variant.name,
+                    );
+                    if let Some(payload_type) = &variant.payload_type {
+                        let type_path = format!("{}.type", variant_path);
+                        self.check_type_expression(file, package, &type_path, payload_type, &[]);
+                    }
+                }
+                for (index, value) in values.iter().enumerate() {
+                    let value_path = format!("{}.values.{}", path, index);
+                    let variant = match self.resolve(package, value) {
+                        Ok(Some(variant)) => variant,
+                        Ok(None) => {
+                            let message = format!(
+                                "unknown type {} in values of {}, unit variants are declared in variants",
+                                value, name
+                            );
+                            self.error(file, &value_path, message);
+                            continue;
+                        }
+                        Err(e) => {
+                            self.error(file, &value_path, e.to_string());
+                            continue;
+                        }
+                    };
+                    // other types than objects are wrapped by their variants
+                    if let Some(CustomType::Object {
+                        name: variant_name,
+                        type_params,
+                        fields,
+                        configs: _,
+                    }) = self.types.get(&variant).map(|(_, _, t)| *t)
+                    {
+                        if type_params.as_ref().is_some_and(|p| !p.is_empty()) {
+                            let message = format!(
+                                "generic object {} cannot be a variant of {}",
+                                variant_name, name
+                            );
+                            self.error(file, &value_path, message);
+                        }
+                        let collision = internal_tag
+                            .and_then(|type_tag| fields.iter().find(|f| &f.name == type_tag));
+                        if let Some(field) = collision {
+                            let message = format!(
+                                "type_tag {} of {} collides with field {}.{}",
+                                field.name, name, variant_name, field.name
+                            );
+                            self.error(file, &format!("{}.type_tag", path), message);
+                        }
+                    }
+                }
+            }
+            CustomType::List { name: _, item_type } => {
+                let item_path = format!("{}.item_type", path);
+                self.check_type_expression(file, package, &item_path, item_type, &[]);
+            }
+            CustomType::Map {
+                name: _,
+                key_type,
+                value_type,
+            } => {
+                let key_path = format!("{}.key_type", path);
+                if let Some(key_type) =
+                    self.check_type_expression(file, package, &key_path, key_type, &[])
+                {
+                    self.check_map_key(file, &key_path, &key_type);
+                }
+                let value_path = format!("{}.value_type", path);
+                self.check_type_expression(file, package, &value_path, value_type, &[]);
+            }
+            CustomType::Alias { name, target_type } | CustomType::Newtype { name, target_type } => {
+                let target_path = format!("{}.target_type", path);
+                self.check_type_expression(file, package, &target_path, target_type, &[]);
+                let full_name = format!("{}.{}", package, name);
+                if self.target_type(&full_name).is_some() && self.wrapped_type(&full_name).is_none()
+                {
+                    let message = format!("{} leads to a cycle of aliases or newtypes", name);
+                    self.error(file, &target_path, message);
+                }
+            }
+        }
+    }
+
+    /// The catch-all variant for values unknown to this version must not clash with a value.
+    fn check_unknown_variant(&mut self, file: usize, name: &str, variants: &[(String, &str)]) {
+        if let Some((path, variant)) = variants
+            .iter()
+            .find(|(_, v)| to_pascal_case(v) == "Unknown")
+        {
+            let message = format!(
+                "{} is reserved for unknown values of {} as it allows them",
+                variant, name
+            );
+            self.error(file, path, message);
+        }
+    }
+
+    /// Variants must stay distinct once their names are turned into identifiers, e.g.
+    /// `read-only` and `ReadOnly` would both become `ReadOnly`.
+    fn check_variant_idents(
+        &mut self,
+        file: usize,
+        name: &str,
+        variants: &[(String, &str, String)],
+    ) {
+        for (index, (path, variant, ident)) in variants.iter().enumerate() {
+            let collision = variants[..index]
+                .iter()
+                .find(|(_, v, i)| i == ident && v != variant);
+            if let Some((_, other, _)) = collision {
+                let message = format!(
+                    "variants {} and {} of {} are both named {}",
+                    other, variant, name, ident
+                );
+                self.error(file, path, message);
+            }
+        }
+    }
+
+    /// Tags set in `tags` must belong to variants, and no two variants may share a tag.
+    fn check_variant_tags(
+        &mut self,
+        file: usize,
+        path: &str,
+        t: &CustomType,
+        variants: &[(String, &str)],
+    ) {
+        let CustomType::ObjectEnum { name, configs, .. } = t else {
+            return;
+        };
+        let tags_path = format!("{}.configs.tags", path);
+        let tags = configs
+            .as_ref()
+            .and_then(|c| c.tags.clone())
+            .unwrap_or_default();
+        let mut unknown_variants = tags
+            .keys()
+            .filter(|v| !variants.iter().any(|(_, n)| n == v))
+            .collect::<Vec<_>>();
+        unknown_variants.sort();
+        for variant in unknown_variants {
+            let message = format!("tag for unknown variant {}.{}", name, variant);
+            self.error(file, &tags_path, message);
+        }
+
+        let rename_all = configs
+            .as_ref()
+            .and_then(|c| c.rename_all.clone())
+            .or_else(|| {
+                let definition = &self.files[file].definition;
+                definition
+                    .configs
+                    .as_ref()
+                    .and_then(|c| c.rename_all.clone())
+            });
+        let mut variant_tags: Vec<(&str, String)> = vec![];
+        for (variant_path, variant) in variants {
+            let tag = match (tags.get(*variant), &rename_all) {
+                (Some(tag), _) => tag.clone(),
+                (None, Some(convention)) => convention.apply(variant),
+                (None, None) => variant.to_string(),
+            };
+            if let Some((other, _)) = variant_tags.iter().find(|(v, t)| *t == tag && v != variant) {
+                let message = format!(
+                    "variants {} and {} of {} have the same tag {}",
+                    other, variant, name, tag
+                );
+                self.error(file, variant_path, message);
+            }
+            variant_tags.push((variant, tag));
+        }
+    }
+
+    fn check_key_field(
+        &mut self,
+        file: usize,
+        package: &str,
+        path: &str,
+        t: &CustomType,
+        key_field: &str,
+    ) {
+        let CustomType::Object {
+            name,
+            type_params,
+            fields,
+            configs: _,
+        } = t
+        else {
+            return;
+        };
+        if type_params.as_ref().is_some_and(|p| !p.is_empty()) {
+            let message = format!("generic object {} cannot have a key field", name);
+            self.error(file, path, message);
+        }
+        let key_type_name = format!("{}.{}Id", package, name);
+        if self.types.contains_key(&key_type_name) {
+            let message = format!(
+                "key type {} of {} collides with an existing type",
+                key_type_name, name
+            );
+            self.error(file, path, message);
+        }
+        let Some(field) = fields.iter().find(|f| f.name == key_field) else {
+            let message = format!("key field {} is not a field of {}", key_field, name);
+            return self.error(file, path, message);
+        };
+        let valid = match field.optional.unwrap_or(false) {
+            true => false,
+            _ => matches!(
+                TypeName::from_str(&field.field_type),
+                Ok(TypeName::Simple(
+                    SimpleType::String
+                        | SimpleType::Int32
+                        | SimpleType::Int64
+                        | SimpleType::UInt32
+                        | SimpleType::UInt64
+                ))
+            ),
+        };
+        if !valid {
+            let message = format!(
+                "key field {}.{} must be a required String or integer",
+                name, key_field
+            );
+            self.error(file, path, message);
+        }
+    }
+
+    /// The type of the key field of an object referenced by `Ref<Object>`, or `None` if the
+    /// object is unknown or has no valid key field.
+    fn key_type(&self, target: &TypeName) -> Option<TypeName> {
+        let TypeName::CustomType(name) = target else {
+            return None;
+        };
+        match self.types.get(name).map(|(_, _, t)| *t) {
+            Some(CustomType::Object {
+                fields,
+                configs: Some(configs),
+                ..
+            }) => {
+                let key_field = configs.key_field.as_ref()?;
+                let field = fields.iter().find(|f| &f.name == key_field)?;
+                TypeName::from_str(&field.field_type).ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// The type wrapped by an alias or a newtype with all wrappers in between skipped, or
+    /// `None` if there is no such type because of a cycle.
+    fn wrapped_type(&self, name: &str) -> Option<TypeName> {
+        let mut visited = vec![name.to_owned()];
+        let mut target = self.target_type(name)?;
+        while let TypeName::CustomType(next) = &target {
+            match self.target_type(next) {
+                Some(_) if visited.contains(next) => return None,
+                Some(next_target) => {
+                    visited.push(next.clone());
+                    target = next_target;
+                }
+                None => break,
+            }
+        }
+        Some(target)
+    }
+
+    /// The resolved target of an alias or a newtype.
+    fn target_type(&self, name: &str) -> Option<TypeName> {
+        let (file, _, t) = self.types.get(name)?;
+        let target_type = match t {
+            CustomType::Alias { target_type, .. } | CustomType::Newtype { target_type, .. } => {
+                target_type
+            }
+            _ => return None,
+        };
+        let package = self.packages[*file].as_ref()?;
+        let type_name = TypeName::from_str(target_type).ok()?;
+        Some(type_name.map_leaves(&mut |t| {
+            match t {
+                TypeName::CustomType(name) => self
+                    .resolve(package, name)
+                    .ok()
+                    .flatten()
+                    .map(TypeName::CustomType),
+                _ => None,
+            }
+        }))
+    }
+
+    /// Parses a type expression and checks that all types it references exist, returning it
+    /// with all references resolved to fully qualified names.
+    fn check_type_expression(
+        &mut self,
+        file: usize,
+        package: &str,
+        path: &str,
+        type_expression: &str,
+        type_params: &[String],
+    ) -> Option<TypeName> {
+        let type_name = match TypeName::from_str(type_expression) {
+            Ok(type_name) => type_name.with_type_params(type_params),
+            Err(e) => {
+                self.error(file, path, e.to_string());
+                return None;
+            }
+        };
+        let mut errors = vec![];
+        let resolved = type_name.map_leaves(&mut |t| {
+            let TypeName::CustomType(name) = t else {
+                return None;
+            };
+            match self.resolve(package, name) {
+                Ok(Some(resolved)) => Some(TypeName::CustomType(resolved)),
+                Ok(None) => {
+                    errors.push(format!("unknown type {}", name));
+                    None
+                }
+                Err(e) => {
+                    errors.push(e.to_string());
+                    None
+                }
+            }
+        });
+        self.check_type_arguments(&resolved, &mut errors);
+        let valid = errors.is_empty();
+        for error in errors {
+            self.error(file, path, error);
+        }
+        valid.then_some(resolved)
+    }
+
+    fn check_type_arguments(&self, type_name: &TypeName, errors: &mut Vec<String>) {
+        let (name, args) = match type_name {
+            TypeName::CustomType(name) => (name, [].as_slice()),
+            TypeName::Generic(name, args) => (name, args.as_slice()),
+            TypeName::List(item) | TypeName::Set(item) | TypeName::Optional(item) => {
+                return self.check_type_arguments(item, errors)
+            }
+            TypeName::Map(key, value) => {
+                if let Err(e) = self.check_map_key_type(key) {
+                    errors.push(e);
+                }
+                self.check_type_arguments(key, errors);
+                return self.check_type_arguments(value, errors);
+            }
+            TypeName::Ref(target) => {
+                let known = match target.as_ref() {
+                    TypeName::CustomType(name) => self.types.contains_key(name),
+                    _ => true,
+                };
+                // unknown types are reported already
+                if known && self.key_type(target).is_none() {
+                    errors.push(format!(
+                        "{} must refer to an object with a key_field",
+                        type_name
+                    ));
+                }
+                return;
+            }
+            _ => return,
+        };
+        let type_params = match self.types.get(name).map(|(_, _, t)| *t) {
+            Some(CustomType::Object { type_params, .. }) => type_params.clone().unwrap_or_default(),
+            Some(_) => vec![],
+            // unknown types are reported already
+            None => return,
+        };
+        if type_params.len() != args.len() {
+            errors.push(format!(
+                "{} expects {} type argument(s) but got {}",
+                name,
+                type_params.len(),
+                args.len()
+            ));
+        }
+        for arg in args {
+            self.check_type_arguments(arg, errors);
+        }
+    }
+
+    fn check_map_key(&mut self, file: usize, path: &str, key_type: &TypeName) {
+        if let Err(message) = self.check_map_key_type(key_type) {
+            self.error(file, path, message);
+        }
+    }
+
+    /// Map keys are object keys on the wire, so they have to be strings.
+    fn check_map_key_type(&self, key_type: &TypeName) -> Result<(), String> {
+        let valid = match key_type {
+            TypeName::Simple(SimpleType::String) => true,
+            TypeName::CustomType(name) => match self.types.get(name) {
+                Some((_, _, CustomType::Alias { .. } | CustomType::Newtype { .. })) => {
+                    match self.wrapped_type(name) {
+                        Some(target) => return self.check_map_key_type(&target),
+                        // cycles are reported already
+                        None => true,
+                    }
+                }
+                Some((_, _, t)) => matches!(t, CustomType::Enum { .. }),
+                // unknown types are reported already
+                None => true,
+            },
+            TypeName::Ref(target) => match self.key_type(target) {
+                Some(key_type) => return self.check_map_key_type(&key_type),
+                // invalid references are reported already
+                None => true,
+            },
+            _ => false,
+        };
+        match valid {
+            true => Ok(()),
+            _ => Err(format!("map keys must be strings, found {}", key_type)),
+        }
+    }
+
+    fn check_default_value(&self, type_name: &TypeName, value: &Any) -> Result<(), String> {
+        let in_range =
+            |min: i128, max: i128| any_to_i128(value).is_some_and(|v| v >= min && v <= max);
+        let is_empty_list = matches!(value, Any::List(l) if l.is_empty());
+        let is_empty_map = matches!(value, Any::Map(m) if m.is_empty());
+        let valid = match type_name {
+            TypeName::Simple(SimpleType::String) => matches!(value, Any::String(_)),
+            TypeName::Simple(SimpleType::Bool) => matches!(value, Any::Bool(_)),
+            TypeName::Simple(SimpleType::Float32 | SimpleType::Float64) => {
+                any_to_f64(value).is_some_and(f64::is_finite)
+            }
+            TypeName::Simple(SimpleType::UInt32) => in_range(0, u32::MAX as i128),
+            TypeName::Simple(SimpleType::UInt64) => in_range(0, u64::MAX as i128),
+            TypeName::Simple(SimpleType::Int32) => in_range(i32::MIN as i128, i32::MAX as i128),
+            TypeName::Simple(SimpleType::Int64) => in_range(i64::MIN as i128, i64::MAX as i128),
+            TypeName::CustomType(name) => match (self.types.get(name).map(|(_, _, t)| *t), value) {
+                (Some(CustomType::Enum { values, .. }), Any::String(s)) => values.contains(s),
+                (Some(CustomType::Enum { .. }), _) => false,
+                (Some(CustomType::List { .. }), _) => is_empty_list,
+                (Some(CustomType::Map { .. }), _) => is_empty_map,
+                (Some(CustomType::Alias { .. } | CustomType::Newtype { .. }), _) => {
+                    match self.wrapped_type(name) {
+                        Some(target) => return self.check_default_value(&target, value),
+                        None => return Err(format!("{} leads to a cycle", name)),
+                    }
+                }
+                _ => return Err(format!("default values are not supported for {}", name)),
+            },
+            TypeName::List(_) | TypeName::Set(_) => is_empty_list,
+            TypeName::Map(_, _) => is_empty_map,
+            TypeName::Optional(item) => return self.check_default_value(item, value),
+            TypeName::Ref(target) => match self.key_type(target) {
+                Some(key_type) => return self.check_default_value(&key_type, value),
+                None => false,
+            },
+            TypeName::Simple(SimpleType::DateTime)
+            | TypeName::Any
+            | TypeName::Generic(_, _)
+            | TypeName::TypeParam(_) => {
+                return Err(format!(
+                    "default values are not supported for {}",
+                    type_name
+                ))
+            }
+        };
+        match valid {
+            true => Ok(()),
+            _ => Err(format!("{:?} is not a valid {} value", value, type_name)),
+        }
+    }
+
+    fn check_identifier(&mut self, file: usize, path: &str, kind: &str, name: &str) {
+        let mut chars = name.chars();
+        let valid = chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            self.error(file, path, format!("invalid {} name: {:?}", kind, name));
+        }
+    }
+
+    /// Names of fields and enum values are escaped by every backend as needed, so they only
+    /// have to be non-empty.
+    fn check_name(&mut self, file: usize, path: &str, kind: &str, name: &str) {
+        if name.trim().is_empty() {
+            self.error(file, path, format!("{} name must not be empty", kind));
+        }
+    }
+
+    fn resolve(&self, package: &str, name: &str) -> anyhow::Result<Option<String>> {
+        resolve_type_reference(name, package, &self.type_packages)
+    }
+
+    fn describe_location(&self, file: usize, path: &str) -> String {
+        let definition_file = &self.files[file];
+        let file_name = definition_file
+            .path
+            .as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| path.to_owned());
+        match definition_file.locate(path) {
+            Some(location) => format!("{}:{}", file_name, location),
+            None => file_name,
+        }
+    }
+
+    fn error(&mut self, file: usize, path: &str, message: String) {
+        let definition_file = &self.files[file];
+        self.diagnostics.push(Diagnostic {
+            file: definition_file.path.clone(),
+            location: definition_file.locate(path),
+            message,
+        });
+    }
+}
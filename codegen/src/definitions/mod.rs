@@ -1,17 +1,34 @@
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct DefinitionConfig {
+    pub package: Option<String>,
+    pub packages: Option<std::collections::HashMap<String, String>>,
     pub rust_package: Option<String>,
+    pub rename_all: Option<crate::definitions::NamingConvention>,
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct TypeConfig {
     pub object_enum_style: Option<crate::definitions::ObjectEnumStyle>,
+    pub key_field: Option<String>,
+    pub rename_all: Option<crate::definitions::NamingConvention>,
+    pub enum_representation: Option<crate::definitions::EnumRepresentation>,
+    pub discriminants: Option<std::collections::HashMap<String, i32>>,
+    pub allow_unknown: Option<bool>,
+    pub object_enum_representation: Option<crate::definitions::ObjectEnumRepresentation>,
+    pub content_key: Option<String>,
+    pub tags: Option<std::collections::HashMap<String, String>>,
+    pub derives: Option<Vec<String>>,
+    pub attributes: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct FieldConfig {
     pub rename: Option<String>,
     pub rust_type_wrapper: Option<crate::definitions::RustTypeWrapper>,
+    pub rust_item_wrapper: Option<crate::definitions::RustTypeWrapper>,
+    pub rust_type: Option<String>,
+    pub serde_with: Option<String>,
+    pub attributes: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -20,26 +37,40 @@ pub struct Field {
     #[serde(rename = "type")]
     pub field_type: String,
     pub optional: Option<bool>,
+    pub default: Option<fluorite::Any>,
     pub configs: Option<crate::definitions::FieldConfig>,
 }
 
 pub type EnumValueList = Vec<String>;
 
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ObjectEnumVariant {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub payload_type: Option<String>,
+}
+
+pub type ObjectEnumVariantList = Vec<crate::definitions::ObjectEnumVariant>;
+
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "type")]
 pub enum CustomType {
     Object {
         name: String,
+        type_params: Option<Vec<String>>,
         fields: crate::definitions::FieldList,
+        configs: Option<crate::definitions::TypeConfig>,
     },
     Enum {
         name: String,
         values: crate::definitions::EnumValueList,
+        configs: Option<crate::definitions::TypeConfig>,
     },
     ObjectEnum {
         name: String,
-        type_tag: String,
-        values: crate::definitions::EnumValueList,
+        type_tag: Option<String>,
+        values: Option<crate::definitions::EnumValueList>,
+        variants: Option<crate::definitions::ObjectEnumVariantList>,
         configs: Option<crate::definitions::TypeConfig>,
     },
     List {
@@ -51,6 +82,14 @@ pub enum CustomType {
         key_type: String,
         value_type: String,
     },
+    Alias {
+        name: String,
+        target_type: String,
+    },
+    Newtype {
+        name: String,
+        target_type: String,
+    },
 }
 pub type CustomTypeList = Vec<crate::definitions::CustomType>;
 
@@ -65,12 +104,16 @@ pub type FieldList = Vec<crate::definitions::Field>;
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum RustTypeWrapper {
     Box,
+    Rc,
+    Arc,
+    Cow,
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct Definition {
+    pub imports: Option<Vec<String>>,
     pub types: crate::definitions::CustomTypeList,
-    pub configs: crate::definitions::DefinitionConfig,
+    pub configs: Option<crate::definitions::DefinitionConfig>,
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -85,3 +128,35 @@ pub enum SimpleType {
     Float32,
     Float64,
 }
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum NamingConvention {
+    #[serde(rename = "camelCase")]
+    CamelCase,
+    #[serde(rename = "snake_case")]
+    SnakeCase,
+    #[serde(rename = "SCREAMING_SNAKE_CASE")]
+    ScreamingSnakeCase,
+    #[serde(rename = "kebab-case")]
+    KebabCase,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum EnumRepresentation {
+    #[serde(rename = "string")]
+    String,
+    #[serde(rename = "int")]
+    Int,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum ObjectEnumRepresentation {
+    #[serde(rename = "internal")]
+    Internal,
+    #[serde(rename = "adjacent")]
+    Adjacent,
+    #[serde(rename = "external")]
+    External,
+    #[serde(rename = "untagged")]
+    Untagged,
+}
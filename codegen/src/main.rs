@@ -1,15 +1,12 @@
 use clap::{Parser, Subcommand};
-use code_gen::{
-    rust::{RustOptions, RustProvider},
-    CodeGenerator,
+use fluorite_codegen::{
+    code_gen::{
+        rust::{RustOptions, RustProvider},
+        CodeGenerator,
+    },
+    load_definition_files, load_definition_files_with_schema_root,
 };
 
-use crate::utils::deserialize_definition_file;
-
-mod code_gen;
-mod definitions;
-mod utils;
-
 #[derive(Debug, Parser)]
 #[clap(author, version, about, long_about = None)]
 struct Args {
@@ -31,6 +28,14 @@ enum Command {
         /// Output codes to a single mod file for each package
         #[clap(short, long, default_value_t = true)]
         single_file: bool,
+
+        /// Infer packages of definitions without one from their paths relative to this directory
+        #[clap(long)]
+        schema_root: Option<String>,
+
+        /// Extra derives for all generated types, e.g. `--derive Eq --derive Hash`
+        #[clap(long = "derive")]
+        derives: Vec<String>,
     },
 }
 fn main() -> anyhow::Result<()> {
@@ -40,17 +45,23 @@ fn main() -> anyhow::Result<()> {
             inputs,
             output,
             single_file,
+            schema_root,
+            derives,
         } => {
-            let definitions = inputs
-                .iter()
-                .map(|f| deserialize_definition_file(f))
-                .collect::<anyhow::Result<Vec<_>>>()?;
+            let definitions = match schema_root {
+                Some(schema_root) => load_definition_files_with_schema_root(&inputs, schema_root)?,
+                None => load_definition_files(&inputs)?,
+            };
 
-            let options = RustOptions::new(output.to_owned()).with_single_file(single_file);
+            let mut options = RustOptions::new(output.to_owned()).with_single_file(single_file);
+            options.derives.extend(derives);
             let config = RustProvider::new(options);
 
             let generator = CodeGenerator::new(Box::new(config));
-            generator.generate(&definitions)?;
+            let report = generator.generate_files(&definitions)?;
+            for note in report.notes() {
+                eprintln!("note: {}", note);
+            }
         }
     }
     Ok(())
@@ -1,51 +1,237 @@
-use std::fs;
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context};
 
 use crate::{
     code_gen::{
         rust::{RustOptions, RustProvider},
-        CodeGenerator,
+        utils::to_snake_case,
+        CodeGenerator, DefinitionFile, GenerationReport,
     },
-    definitions::Definition,
+    definitions::{Definition, DefinitionConfig},
 };
 
-pub fn compile_with_options(options: RustOptions, inputs: &[&str]) -> anyhow::Result<()> {
-    let definitions = inputs
-        .iter()
-        .map(|s| deserialize_definition_file(s))
-        .collect::<anyhow::Result<Vec<Definition>>>()?;
+pub fn compile_with_options(
+    options: RustOptions,
+    inputs: &[&str],
+) -> anyhow::Result<GenerationReport> {
+    let definitions = load_definition_files(inputs)?;
     let config = RustProvider::new(options);
 
     let generator = CodeGenerator::new(Box::new(config));
-    generator.generate(&definitions)?;
-    Ok(())
+    generator.generate_files(&definitions)
 }
 
-pub fn compile(inputs: &[&str], output: &str) -> anyhow::Result<()> {
-    let definitions = inputs
-        .iter()
-        .map(|s| deserialize_definition_file(s))
-        .collect::<anyhow::Result<Vec<Definition>>>()?;
+/// Like [`compile_with_options`], but definitions without a `package` get one inferred from
+/// their location under `schema_root`.
+pub fn compile_with_schema_root(
+    options: RustOptions,
+    schema_root: &str,
+    inputs: &[&str],
+) -> anyhow::Result<GenerationReport> {
+    let definitions = load_definition_files_with_schema_root(inputs, schema_root)?;
+    let config = RustProvider::new(options);
+
+    let generator = CodeGenerator::new(Box::new(config));
+    generator.generate_files(&definitions)
+}
+
+pub fn compile(inputs: &[&str], output: &str) -> anyhow::Result<GenerationReport> {
+    let definitions = load_definition_files(inputs)?;
     let options = RustOptions::new(output.to_owned());
     let config = RustProvider::new(options);
 
     let generator = CodeGenerator::new(Box::new(config));
-    generator.generate(&definitions)?;
-    Ok(())
+    generator.generate_files(&definitions)
 }
 
-pub(crate) fn deserialize_definition_file(file_path: &str) -> anyhow::Result<Definition> {
+pub fn deserialize_definition_file<P: AsRef<Path>>(file_path: P) -> anyhow::Result<Definition> {
     let file_content = fs::read_to_string(file_path)?;
     let r = serde_yaml::from_str(&file_content)?;
     Ok(r)
 }
 
+/// Loads the given definition files together with everything they import, recursively.
+/// Imports are resolved relative to the importing file and every file is loaded only once, so
+/// files may import each other.
+pub fn load_definition_files<P: AsRef<Path>>(inputs: &[P]) -> anyhow::Result<Vec<DefinitionFile>> {
+    let mut loader = DefinitionLoader::default();
+    for input in inputs {
+        loader.load(input.as_ref())?;
+    }
+    Ok(loader.definitions)
+}
+
+/// Like [`load_definition_files`], but definitions without a `package` get one inferred from
+/// their path relative to `schema_root`, e.g. `billing/invoice.yml` is in `billing.invoice`.
+pub fn load_definition_files_with_schema_root<P: AsRef<Path>, R: AsRef<Path>>(
+    inputs: &[P],
+    schema_root: R,
+) -> anyhow::Result<Vec<DefinitionFile>> {
+    let schema_root = schema_root.as_ref();
+    let schema_root = fs::canonicalize(schema_root)
+        .with_context(|| format!("Cannot find schema root: {}", schema_root.display()))?;
+    let mut loader = DefinitionLoader {
+        schema_root: Some(schema_root),
+        ..Default::default()
+    };
+    for input in inputs {
+        loader.load(input.as_ref())?;
+    }
+    Ok(loader.definitions)
+}
+
+#[derive(Default)]
+struct DefinitionLoader {
+    schema_root: Option<PathBuf>,
+    // files that are loaded or whose imports are being loaded, so import cycles terminate
+    loaded: HashSet<PathBuf>,
+    definitions: Vec<DefinitionFile>,
+}
+
+impl DefinitionLoader {
+    fn load(&mut self, file_path: &Path) -> anyhow::Result<()> {
+        let path = fs::canonicalize(file_path)
+            .with_context(|| format!("Cannot find definition file: {}", file_path.display()))?;
+        if !self.loaded.insert(path.clone()) {
+            return Ok(());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Cannot load definition file: {}", path.display()))?;
+        let mut definition: Definition = serde_yaml::from_str(&content)
+            .with_context(|| format!("Cannot load definition file: {}", path.display()))?;
+        if let Some(schema_root) = &self.schema_root {
+            let configs = definition.configs.get_or_insert(DefinitionConfig {
+                package: None,
+                packages: None,
+                rust_package: None,
+                rename_all: None,
+            });
+            if configs.package.is_none() {
+                configs.package = Some(infer_package(&path, schema_root)?);
+            }
+        }
+        let base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        for import in definition.imports.iter().flatten() {
+            self.load(&base_dir.join(import))?;
+        }
+        self.definitions
+            .push(DefinitionFile::new(path, &content, definition));
+        Ok(())
+    }
+}
+
+fn infer_package(path: &Path, schema_root: &Path) -> anyhow::Result<String> {
+    let relative = path.strip_prefix(schema_root).map_err(|_| {
+        anyhow!(
+            "Definition file {} is not under schema root {}",
+            path.display(),
+            schema_root.display()
+        )
+    })?;
+    let package = relative
+        .with_extension("")
+        .components()
+        .map(|c| to_snake_case(&c.as_os_str().to_string_lossy()))
+        .collect::<Vec<_>>();
+    Ok(package.join("."))
+}
+
 #[cfg(test)]
 mod test {
-    use super::deserialize_definition_file;
+    use std::fs;
+
+    use super::{
+        deserialize_definition_file, load_definition_files, load_definition_files_with_schema_root,
+    };
+    use crate::definitions::CustomType;
 
     #[test]
     fn test_deserialize_definition_file() -> anyhow::Result<()> {
         deserialize_definition_file("../examples/users.yml")?;
         Ok(())
     }
+
+    #[test]
+    fn test_load_definition_files_with_imports() -> anyhow::Result<()> {
+        let definitions = load_definition_files(&["../examples/orders.yml"])?;
+        assert_eq!(definitions.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_definition_files_tolerates_import_cycles() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir().join("fluorite_import_cycle");
+        fs::create_dir_all(&dir)?;
+        fs::write(
+            dir.join("a.yml"),
+            r#"imports: [b.yml]
+configs: { package: orders }
+types:
+  - name: Order
+    type: Object
+    fields:
+      - name: shipping
+        type: shipping.Shipping
+"#,
+        )?;
+        fs::write(
+            dir.join("b.yml"),
+            r#"imports: [a.yml]
+configs: { package: shipping }
+types:
+  - name: Shipping
+    type: Object
+    fields:
+      - name: orders
+        type: List<orders.Order>
+"#,
+        )?;
+        let definitions = load_definition_files(&[dir.join("a.yml"), dir.join("b.yml")])?;
+        let types = definitions
+            .iter()
+            .flat_map(|d| d.definition.types.iter())
+            .filter_map(|t| match t {
+                CustomType::Object { name, .. } => Some(name.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(types, vec!["Shipping", "Order"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_definition_files_infers_packages() -> anyhow::Result<()> {
+        let root = std::env::temp_dir().join("fluorite_schema_root");
+        fs::create_dir_all(root.join("billing"))?;
+        fs::create_dir_all(root.join("shared"))?;
+        fs::write(
+            root.join("billing/invoice.yml"),
+            "imports: [../shared/MoneyTypes.yml]\ntypes: []\n",
+        )?;
+        fs::write(
+            root.join("shared/MoneyTypes.yml"),
+            "types: []\nconfigs: { package: money }\n",
+        )?;
+        let definitions =
+            load_definition_files_with_schema_root(&[root.join("billing/invoice.yml")], &root)?;
+        let packages = definitions
+            .iter()
+            .map(|d| d.definition.package_for("rust"))
+            .collect::<Vec<_>>();
+        assert_eq!(packages, vec![Some("money"), Some("billing.invoice")]);
+
+        let err = load_definition_files_with_schema_root(
+            &[root.join("billing/invoice.yml")],
+            root.join("shared"),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("is not under schema root"));
+        Ok(())
+    }
 }
@@ -2,10 +2,11 @@ use std::fs;
 
 use fluorite_codegen::{
     code_gen::{
-        rust::{RustOptions, RustProvider},
+        rust::{RustContext, RustOptions, RustProvider},
         CodeGenerator,
     },
     definitions::Definition,
+    load_definition_files,
 };
 
 pub(crate) fn deserialize_definition_file(file_path: &str) -> anyhow::Result<Definition> {
@@ -14,6 +15,51 @@ pub(crate) fn deserialize_definition_file(file_path: &str) -> anyhow::Result<Def
     Ok(r)
 }
 
+/// Parses a definition with the given `configs` mapping and the yaml list of its `types`.
+fn definition(configs: &str, types: &str) -> anyhow::Result<Definition> {
+    let definition = serde_yaml::from_str(&format!("configs: {}\ntypes:\n{}", configs, types))?;
+    Ok(definition)
+}
+
+/// An output directory for the code generated by one test.
+fn output_dir(name: &str) -> String {
+    std::env::temp_dir().join(name).display().to_string()
+}
+
+fn generator(output_dir: &str) -> CodeGenerator<RustContext> {
+    CodeGenerator::new(Box::new(RustProvider::new(RustOptions::new(
+        output_dir.to_owned(),
+    ))))
+}
+
+/// Writes the yaml list of `types` to a definition file named `name`, starting at its fourth line,
+/// and checks that validating it reports every problem of `expected`, given as
+/// `line:column: error: message`.
+fn assert_validation_errors(name: &str, types: &str, expected: &[&str]) -> anyhow::Result<()> {
+    let dir = std::env::temp_dir().join("fluorite_validation");
+    fs::create_dir_all(&dir)?;
+    let file = dir.join(format!("{}.yml", name));
+    fs::write(
+        &file,
+        format!("configs:\n  rust_package: broken\ntypes:\n{}", types),
+    )?;
+    let definitions = load_definition_files(&[&file])?;
+    let err = generator(&output_dir("fluorite_validation_output"))
+        .generate_files(&definitions)
+        .unwrap_err();
+    let file = fs::canonicalize(&file)?.display().to_string();
+    let message = err.to_string();
+    for line in expected {
+        assert!(
+            message.contains(&format!("{}:{}", file, line)),
+            "{} not found in {}",
+            line,
+            message
+        );
+    }
+    Ok(())
+}
+
 #[test]
 fn test_rust_code_gen() -> anyhow::Result<()> {
     let d1 = deserialize_definition_file("../examples/users.yml")?;
@@ -23,6 +69,714 @@ fn test_rust_code_gen() -> anyhow::Result<()> {
     let config = RustProvider::new(options);
 
     let generator = CodeGenerator::new(Box::new(config));
-    generator.generate(&vec![d1, d2])?;
+    generator.generate(&[d1, d2])?;
+    Ok(())
+}
+
+#[test]
+fn test_rust_code_gen_rejects_invalid_default() -> anyhow::Result<()> {
+    let definition = definition(
+        "{ rust_package: defaults }",
+        r#"
+  - name: Settings
+    type: Object
+    fields:
+      - name: retries
+        type: UInt32
+        default: "three"
+"#,
+    )?;
+    let err = generator(&output_dir("fluorite_defaults"))
+        .generate(&[definition])
+        .unwrap_err();
+    assert!(err.to_string().contains("Settings.retries"));
+    Ok(())
+}
+
+#[test]
+fn test_rust_code_gen_resolves_package_qualified_types() -> anyhow::Result<()> {
+    let status = |package: &str| {
+        definition(
+            &format!("{{ rust_package: {} }}", package),
+            "  - name: Status\n    type: Enum\n    values: [Active]\n",
+        )
+    };
+    let holder = |reference: &str| {
+        definition(
+            "{ rust_package: holders }",
+            &format!(
+                "  - name: Holder\n    type: Object\n    fields:\n      - name: status\n        type: {}\n",
+                reference
+            ),
+        )
+    };
+    let output_dir = output_dir("fluorite_packages");
+    let generator = generator(&output_dir);
+
+    generator.generate(&[
+        status("billing")?,
+        status("shipping")?,
+        holder("shipping.Status")?,
+    ])?;
+    let generated = fs::read_to_string(format!("{}/holders/mod.rs", output_dir))?;
+    assert!(generated.contains("pub status: crate::shipping::Status,"));
+
+    let err = generator
+        .generate(&[status("billing")?, status("shipping")?, holder("Status")?])
+        .unwrap_err();
+    assert!(err.to_string().contains("Ambiguous type reference Status"));
+    Ok(())
+}
+
+#[test]
+fn test_rust_code_gen_reports_all_problems() -> anyhow::Result<()> {
+    assert_validation_errors(
+        "problems",
+        r#"  - name: User
+    type: Object
+    fields:
+      - name: ""
+        type: String
+      - name: address
+        type: Adress
+      - name: address
+        type: Map<UInt32, String>
+  - name: Gender
+    type: Enum
+    values: [Male, Female, Male]
+  - name: User
+    type: List
+    item_type: User
+"#,
+        &[
+            "7:15: error: field name must not be empty",
+            "10:15: error: unknown type Adress",
+            "11:15: error: duplicate field address in User",
+            "12:15: error: map keys must be strings, found UInt32",
+            "15:28: error: duplicate enum value Male in Gender",
+            "16:11: error: duplicate type broken.User, first defined at",
+        ],
+    )
+}
+
+#[test]
+fn test_rust_code_gen_rejects_unknown_object_enum_values() -> anyhow::Result<()> {
+    assert_validation_errors(
+        "object_enum_values",
+        r#"  - name: Address
+    type: ObjectEnum
+    type_tag: type
+    values: [Empty]
+"#,
+        &["7:14: error: unknown type Empty in values of Address, unit variants are declared in variants"],
+    )
+}
+
+#[test]
+fn test_rust_code_gen_rejects_colliding_variant_idents() -> anyhow::Result<()> {
+    assert_validation_errors(
+        "variant_idents",
+        r#"  - name: Access
+    type: Enum
+    values: [read-only, ReadOnly, http_only, HTTPOnly]
+  - name: Event
+    type: ObjectEnum
+    type_tag: type
+    variants:
+      - name: log-in
+      - name: LogIn
+"#,
+        &[
+            "6:25: error: variants read-only and ReadOnly of Access are both named ReadOnly",
+            "6:46: error: variants http_only and HTTPOnly of Access are both named HttpOnly",
+            "12:15: error: variants log-in and LogIn of Event are both named LogIn",
+        ],
+    )
+}
+
+#[test]
+fn test_rust_code_gen_rejects_alias_cycles() -> anyhow::Result<()> {
+    assert_validation_errors(
+        "alias_cycles",
+        r#"  - name: A
+    type: Alias
+    target_type: B
+  - name: B
+    type: Newtype
+    target_type: A
+"#,
+        &[
+            "6:18: error: A leads to a cycle of aliases or newtypes",
+            "9:18: error: B leads to a cycle of aliases or newtypes",
+        ],
+    )
+}
+
+#[test]
+fn test_rust_code_gen_rejects_invalid_refs() -> anyhow::Result<()> {
+    assert_validation_errors(
+        "refs",
+        r#"  - name: Gender
+    type: Enum
+    values: [Male, Female]
+  - name: Team
+    type: Object
+    configs:
+      key_field: name
+    fields:
+      - name: slug
+        type: String
+      - name: owner
+        type: Ref<Gender>
+"#,
+        &[
+            "10:18: error: key field name is not a field of Team",
+            "15:15: error: Ref<broken.Gender> must refer to an object with a key_field",
+        ],
+    )
+}
+
+#[test]
+fn test_rust_code_gen_rejects_invalid_discriminants() -> anyhow::Result<()> {
+    assert_validation_errors(
+        "discriminants",
+        r#"  - name: Level
+    type: Enum
+    configs:
+      discriminants: { Low: 1, Mid: 1, Top: 2 }
+    values: [Low, Mid]
+"#,
+        &[
+            "7:22: error: discriminant for unknown value Level.Top",
+            "7:22: error: invalid discriminants of Level: Low and Mid have the same discriminant 1",
+        ],
+    )
+}
+
+#[test]
+fn test_rust_code_gen_rejects_invalid_unknown_values() -> anyhow::Result<()> {
+    assert_validation_errors(
+        "unknown_values",
+        r#"  - name: Level
+    type: Enum
+    configs:
+      discriminants: { Low: 1 }
+      allow_unknown: true
+    values: [Low]
+  - name: Status
+    type: Enum
+    configs:
+      allow_unknown: true
+    values: [Active, Unknown]
+"#,
+        &[
+            "8:22: error: Level cannot allow unknown values as it has discriminants",
+            "14:22: error: Unknown is reserved for unknown values of Status as it allows them",
+        ],
+    )
+}
+
+#[test]
+fn test_rust_code_gen_requires_type_tags() -> anyhow::Result<()> {
+    assert_validation_errors(
+        "type_tags",
+        r#"  - name: Created
+    type: Object
+    fields: []
+  - name: Event
+    type: ObjectEnum
+    configs:
+      object_enum_representation: adjacent
+    values: [Created]
+"#,
+        &["7:11: error: type_tag is required by internally or adjacently tagged Event"],
+    )
+}
+
+#[test]
+fn test_rust_code_gen_rejects_invalid_variants() -> anyhow::Result<()> {
+    assert_validation_errors(
+        "variants",
+        r#"  - name: User
+    type: Object
+    fields: []
+  - name: Event
+    type: ObjectEnum
+    type_tag: type
+    values: [User]
+    variants:
+      - name: User
+        type: Strin
+"#,
+        &[
+            "12:15: error: duplicate enum value User in Event",
+            "13:15: error: unknown type Strin",
+        ],
+    )
+}
+
+#[test]
+fn test_rust_code_gen_rejects_invalid_tags() -> anyhow::Result<()> {
+    assert_validation_errors(
+        "tags",
+        r#"  - name: Place
+    type: ObjectEnum
+    type_tag: kind
+    configs:
+      tags: { Nope: x, B: a }
+    variants:
+      - name: a
+      - name: B
+"#,
+        &[
+            "8:13: error: tag for unknown variant Place.Nope",
+            "11:15: error: variants a and B of Place have the same tag a",
+        ],
+    )
+}
+
+#[test]
+fn test_rust_code_gen_rejects_misplaced_item_wrappers() -> anyhow::Result<()> {
+    assert_validation_errors(
+        "item_wrappers",
+        r#"  - name: Holder
+    type: Object
+    fields:
+      - name: item
+        type: String
+        configs:
+          rust_item_wrapper: Rc
+"#,
+        &["10:30: error: rust_item_wrapper of field Holder.item requires a List, Set or Map, found String"],
+    )
+}
+
+#[test]
+fn test_rust_code_gen_rejects_invalid_rust_types() -> anyhow::Result<()> {
+    assert_validation_errors(
+        "rust_types",
+        r#"  - name: Holder
+    type: Object
+    fields:
+      - name: id
+        type: String
+        default: ""
+        configs:
+          rust_type: uuid::Uuid
+      - name: ids
+        type: List<String>
+        configs:
+          rust_type: Vec<uuid::Uuid>
+          rust_item_wrapper: Rc
+"#,
+        &[
+            "11:22: error: field Holder.id cannot have a default value with a rust_type",
+            "15:22: error: field Holder.ids cannot have both rust_type and rust_item_wrapper",
+        ],
+    )
+}
+
+#[test]
+fn test_rust_code_gen_uses_per_language_packages() -> anyhow::Result<()> {
+    let definition = |configs: &str| {
+        definition(
+            configs,
+            "  - name: Event\n    type: Object\n    fields:\n      - name: id\n        type: String\n",
+        )
+    };
+    let output_dir = output_dir("fluorite_language_packages");
+    let generator = generator(&output_dir);
+
+    generator.generate(&[definition("{ package: events }")?])?;
+    assert!(fs::metadata(format!("{}/events/mod.rs", output_dir)).is_ok());
+
+    generator.generate(&[definition(
+        "{ package: events, packages: { rust: rust_events, ts: \"@acme/events\" } }",
+    )?])?;
+    assert!(fs::metadata(format!("{}/rust_events/mod.rs", output_dir)).is_ok());
+
+    let err = generator
+        .generate(&[definition("{ packages: { ts: \"@acme/events\" } }")?])
+        .unwrap_err();
+    assert!(err.to_string().contains("packages.rust"));
+    Ok(())
+}
+
+#[test]
+fn test_rust_code_gen_configures_derives() -> anyhow::Result<()> {
+    let definition: Definition = serde_yaml::from_str(
+        r#"
+configs:
+  rust_package: derives
+types:
+  - name: Point
+    type: Object
+    configs:
+      derives: [Default]
+      attributes: [serde(deny_unknown_fields)]
+    fields:
+      - name: x
+        type: Float64
+  - name: Tag
+    type: Object
+    configs:
+      derives: [PartialEq]
+    fields:
+      - name: name
+        type: String
+        configs:
+          attributes: [serde(alias = "label")]
+      - name: labels
+        type: Map<String, String>
+  - name: Key
+    type: Newtype
+    target_type: String
+  - name: Level
+    type: Enum
+    values: [Low, High]
+"#,
+    )?;
+    let output_dir = output_dir("fluorite_derives");
+    let options = RustOptions::new(output_dir.clone())
+        .with_derives(&["Debug", "Clone"])
+        .with_attributes(&["non_exhaustive"]);
+    let generator = CodeGenerator::new(Box::new(RustProvider::new(options)));
+    generator.generate(&[definition])?;
+
+    let generated = fs::read_to_string(format!("{}/derives/mod.rs", output_dir))?;
+    assert!(!generated.contains("derive_new"));
+    assert!(generated.contains(
+        "#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default)]\n#[non_exhaustive]\n#[serde(deny_unknown_fields)]\npub struct Point"
+    ));
+    assert!(generated.contains(
+        "#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Eq, PartialEq)]\n#[non_exhaustive]\npub struct Tag"
+    ));
+    assert!(generated.contains("  #[serde(alias = \"label\")]\n  pub name: String,"));
+    assert!(generated.contains(
+        "#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]\n#[non_exhaustive]\n#[serde(transparent)]\npub struct Key"
+    ));
+    assert!(generated.contains(
+        "#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Copy)]\n#[non_exhaustive]\npub enum Level"
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_rust_code_gen_boxes_recursive_types() -> anyhow::Result<()> {
+    let definition = |types: &str| definition("{ rust_package: trees }", types);
+    let output_dir = output_dir("fluorite_recursion");
+    let generator = generator(&output_dir);
+
+    let report = generator.generate(&[definition(
+        r#"
+  - name: Node
+    type: Object
+    fields:
+      - name: next
+        type: Node
+        optional: true
+      - name: children
+        type: List<Node>
+"#,
+    )?])?;
+    let generated = fs::read_to_string(format!("{}/trees/mod.rs", output_dir))?;
+    assert!(generated.contains("pub next: Box<Option<crate::trees::Node>>,"));
+    assert!(generated.contains("pub children: Vec<crate::trees::Node>,"));
+    assert_eq!(report.boxed_fields, vec!["trees.Node.next".to_owned()]);
+    assert_eq!(
+        report.notes(),
+        vec!["boxed trees.Node.next to break a recursive type".to_owned()]
+    );
+
+    // a field boxed by hand already breaks the recursion and is not boxed again
+    let report = generator.generate(&[definition(
+        r#"
+  - name: Order
+    type: Object
+    fields:
+      - name: shipping
+        type: Shipping
+        optional: true
+        configs:
+          rust_type_wrapper: Box
+  - name: Shipping
+    type: Object
+    fields:
+      - name: order
+        type: Order
+"#,
+    )?])?;
+    let generated = fs::read_to_string(format!("{}/trees/mod.rs", output_dir))?;
+    assert!(generated.contains("pub shipping: Box<Option<crate::trees::Shipping>>,"));
+    assert!(generated.contains("pub order: crate::trees::Order,"));
+    assert!(report.boxed_fields.is_empty());
+
+    let err = generator
+        .generate(&[definition(
+            r#"
+  - name: Expr
+    type: ObjectEnum
+    type_tag: type
+    variants:
+      - name: neg
+        type: Expr
+"#,
+        )?])
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("trees.Expr contains itself through types other than objects"));
+    Ok(())
+}
+
+#[test]
+fn test_rust_code_gen_escapes_wire_names() -> anyhow::Result<()> {
+    let definition = definition(
+        "{ rust_package: wire }",
+        r#"
+  - name: Quote
+    type: Object
+    fields:
+      - name: say "hi"
+        type: String
+      - name: back\slash
+        type: String
+  - name: Mark
+    type: Enum
+    values: [say "hi", back\slash]
+  - name: Event
+    type: ObjectEnum
+    type_tag: ty"pe
+    variants:
+      - name: a"b
+        type: String
+"#,
+    )?;
+    let output_dir = output_dir("fluorite_wire_names");
+    generator(&output_dir).generate(&[definition])?;
+
+    let generated = fs::read_to_string(format!("{}/wire/mod.rs", output_dir))?;
+    for line in [
+        r#"  #[serde(rename = "say \"hi\"")]"#,
+        r#"  #[serde(rename = "back\\slash")]"#,
+        r#"#[serde(tag = "ty\"pe")]"#,
+        r#"  #[serde(rename = "a\"b")]"#,
+        r#"      Mark::SayHi => "say \"hi\"","#,
+        r#"      "back\\slash" => Ok(Mark::BackSlash),"#,
+    ] {
+        assert!(generated.contains(line), "{} not found", line);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_rust_code_gen_writes_enum_names() -> anyhow::Result<()> {
+    let definition = definition(
+        "{ rust_package: names }",
+        r#"
+  - name: Level
+    type: Enum
+    values: [Low, high-ish]
+  - name: Status
+    type: Enum
+    configs:
+      allow_unknown: true
+    values: [Active]
+"#,
+    )?;
+    let output_dir = output_dir("fluorite_enum_names");
+    generator(&output_dir).generate(&[definition])?;
+
+    let generated = fs::read_to_string(format!("{}/names/mod.rs", output_dir))?;
+    assert!(generated
+        .contains("serde::Deserialize, Copy, Eq, Hash, PartialOrd, Ord)]\npub enum Level {"));
+    assert!(generated.contains(
+        "  pub const ALL: &'static [Self] = &[\n    Level::Low,\n    Level::HighIsh,\n  ];"
+    ));
+    assert!(generated.contains(
+        "  pub fn as_str(&self) -> &'static str {\n    match self {\n      Level::Low => \"Low\",\n      Level::HighIsh => \"high-ish\",\n    }\n  }"
+    ));
+    assert!(generated.contains(
+        "impl std::fmt::Display for Level {\n  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {\n    f.write_str(self.as_str())\n  }\n}"
+    ));
+    assert!(generated.contains(
+        "impl std::str::FromStr for Level {\n  type Err = fluorite::ParseEnumError;\n\n  fn from_str(s: &str) -> Result<Self, Self::Err> {\n    match s {\n      \"Low\" => Ok(Level::Low),\n      \"high-ish\" => Ok(Level::HighIsh),\n      _ => Err(fluorite::ParseEnumError::new(\"Level\", s)),\n    }\n  }\n}"
+    ));
+    // unknown values are kept by name, so they are neither static nor Copy
+    assert!(generated.contains("serde::Deserialize, Eq, Hash, PartialOrd, Ord)]\n"));
+    assert!(generated.contains("  pub fn as_str(&self) -> &str {"));
+    assert!(generated.contains("      Status::Unknown(value) => value,"));
+    assert!(generated.contains("      _ => Ok(Status::Unknown(s.to_owned())),"));
+    Ok(())
+}
+
+#[test]
+fn test_rust_code_gen_writes_builders() -> anyhow::Result<()> {
+    let audit = definition(
+        "{ rust_package: builders }",
+        r#"
+  - name: Audit
+    type: Object
+    fields:
+      - name: id
+        type: String
+      - name: note
+        type: String
+        optional: true
+      - name: retries
+        type: UInt32
+        default: 3
+  - name: Login
+    type: Object
+    fields:
+      - name: type
+        type: String
+      - name: 2fa-code
+        type: String
+"#,
+    )?;
+    let output_dir = output_dir("fluorite_builders");
+    generator(&output_dir).generate(&[audit])?;
+
+    let generated = fs::read_to_string(format!("{}/builders/mod.rs", output_dir))?;
+    assert!(generated.contains(
+        "pub struct AuditBuilder {\n  id: Option<String>,\n  note: Option<Option<String>>,\n  retries: Option<u32>,\n}"
+    ));
+    assert!(generated.contains(
+        "impl Audit {\n  pub fn builder() -> AuditBuilder {\n    AuditBuilder {\n      id: None,\n      note: None,\n      retries: None,\n    }\n  }\n}"
+    ));
+    assert!(generated.contains(
+        "  pub fn id(mut self, value: impl Into<String>) -> Self {\n    self.id = Some(value.into());\n    self\n  }"
+    ));
+    // optional fields are set to their values, not to options
+    assert!(generated.contains(
+        "  pub fn note(mut self, value: impl Into<String>) -> Self {\n    self.note = Some(Some(value.into()));\n    self\n  }"
+    ));
+    assert!(generated.contains(
+        "  pub fn build(self) -> Result<Audit, fluorite::BuildError> {\n    Ok(Audit {\n      id: self.id.ok_or_else(|| fluorite::BuildError::new(\"Audit\", \"id\"))?,\n      note: self.note.unwrap_or_default(),\n      retries: self.retries.unwrap_or_else(default_audit_retries),\n    })\n  }"
+    ));
+    // unset fields are reported by their names in the schema
+    assert!(generated.contains(
+        "      r#type: self.r#type.ok_or_else(|| fluorite::BuildError::new(\"Login\", \"type\"))?,\n      _2fa_code: self._2fa_code.ok_or_else(|| fluorite::BuildError::new(\"Login\", \"2fa-code\"))?,"
+    ));
+
+    let err = generator(&output_dir)
+        .generate(&[definition(
+            "{ rust_package: builders }",
+            "  - name: Job\n    type: Object\n    fields:\n      - name: build\n        type: String\n",
+        )?])
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Field build of Job collides with the build() of its builder"));
+    Ok(())
+}
+
+#[test]
+fn test_rust_code_gen_writes_standalone_inline_objects() -> anyhow::Result<()> {
+    let definition = definition(
+        "{ rust_package: standalone }",
+        r#"
+  - name: Event
+    type: ObjectEnum
+    type_tag: type
+    values: [Created, Deleted]
+  - name: Created
+    type: Object
+    fields:
+      - name: id
+        type: String
+  - name: Deleted
+    type: Object
+    fields:
+      - name: id
+        type: String
+  - name: Audit
+    type: Object
+    fields:
+      - name: created
+        type: List<Created>
+"#,
+    )?;
+    let output_dir = output_dir("fluorite_standalone");
+    generator(&output_dir).generate(&[definition])?;
+
+    let generated = fs::read_to_string(format!("{}/standalone/mod.rs", output_dir))?;
+    // only objects referenced outside of the enum get a struct of their own
+    assert!(generated.contains("pub struct Created {\n  pub id: String,\n}"));
+    assert!(!generated.contains("pub struct Deleted"));
+    assert!(generated.contains("pub created: Vec<crate::standalone::Created>,"));
+    assert!(generated.contains(
+        "pub enum Event {\n  Created {\n    id: String,\n  },\n  Deleted {\n    id: String,\n  },\n}"
+    ));
+    assert!(generated.contains(
+        "impl From<crate::standalone::Created> for Event {\n  fn from(value: crate::standalone::Created) -> Self {\n    let crate::standalone::Created { id } = value;\n    Event::Created { id }\n  }\n}"
+    ));
+    assert!(generated.contains(
+        "impl TryFrom<Event> for crate::standalone::Created {\n  type Error = Event;\n\n  fn try_from(value: Event) -> Result<Self, Self::Error> {\n    #[allow(unreachable_patterns)]\n    match value {\n      Event::Created { id } => Ok(crate::standalone::Created { id }),\n      other => Err(other),\n    }\n  }\n}"
+    ));
+    assert!(!generated.contains("impl From<crate::standalone::Deleted>"));
+    Ok(())
+}
+
+#[test]
+fn test_rust_code_gen_writes_object_enum_helpers() -> anyhow::Result<()> {
+    let definition = definition(
+        "{ rust_package: helpers }",
+        r#"
+  - name: Event
+    type: ObjectEnum
+    type_tag: type
+    values: [Moved, Cleared, Note]
+    variants:
+      - name: ping
+      - name: count
+        type: UInt32
+  - name: Moved
+    type: Object
+    fields:
+      - name: from
+        type: String
+      - name: to
+        type: String
+  - name: Cleared
+    type: Object
+    fields: []
+  - name: Note
+    type: Newtype
+    target_type: String
+"#,
+    )?;
+    let output_dir = output_dir("fluorite_object_enum_helpers");
+    generator(&output_dir).generate(&[definition])?;
+
+    let generated = fs::read_to_string(format!("{}/helpers/mod.rs", output_dir))?;
+    assert!(generated
+        .contains("pub enum EventKind {\n  Moved,\n  Cleared,\n  Note,\n  Ping,\n  Count,\n}"));
+    assert!(generated.contains("      Event::Ping => EventKind::Ping,\n"));
+    assert!(generated.contains(
+        "  pub fn is_count(&self) -> bool {\n    matches!(self, Event::Count { .. })\n  }"
+    ));
+    // inline objects return their fields
+    assert!(generated.contains(
+        "  pub fn as_moved(&self) -> Option<(&String, &String)> {\n    match self {\n      Event::Moved { from, to } => Some((from, to)),"
+    ));
+    assert!(generated
+        .contains("  pub fn as_moved_mut(&mut self) -> Option<(&mut String, &mut String)> {"));
+    assert!(generated.contains(
+        "  pub fn into_moved(self) -> Result<(String, String), Self> {\n    match self {\n      Event::Moved { from, to } => Ok((from, to)),"
+    ));
+    assert!(generated.contains(
+        "  pub fn into_cleared(self) -> Result<(), Self> {\n    match self {\n      Event::Cleared {} => Ok(()),"
+    ));
+    // wrapped values return their payloads
+    assert!(generated.contains(
+        "  pub fn as_count_mut(&mut self) -> Option<&mut u32> {\n    match self {\n      Event::Count { content: value } => Some(value),"
+    ));
+    assert!(generated.contains("  pub fn into_note(self) -> Result<crate::helpers::Note, Self> {"));
+    assert!(generated.contains(
+        "impl From<crate::helpers::Note> for Event {\n  fn from(value: crate::helpers::Note) -> Self {\n    Event::Note { content: value }\n  }\n}"
+    ));
+    assert!(!generated.contains("pub fn as_ping"));
     Ok(())
 }
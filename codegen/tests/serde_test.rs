@@ -6,21 +6,30 @@ fn can_serialize_and_deserialize() -> anyhow::Result<()> {
         name: "name".to_string(),
         field_type: "String".to_string(),
         optional: Some(true),
+        default: None,
         configs: None,
     };
     let fields = vec![field];
     let user_type = CustomType::Object {
         name: "User".to_string(),
+        type_params: None,
         fields,
+        configs: None,
     };
     let definition = Definition {
+        imports: None,
         types: vec![user_type],
-        configs: DefinitionConfig { rust_package: None },
+        configs: Some(DefinitionConfig {
+            package: Some("users".to_string()),
+            packages: None,
+            rust_package: None,
+            rename_all: None,
+        }),
     };
 
     let serialized = serde_yaml::to_string(&definition)?;
     println!("serialized: {}", serialized);
-    serde_yaml::from_str(serialized.as_str())?;
+    serde_yaml::from_str::<Definition>(serialized.as_str())?;
     // assert_eq!(definition, deserialized);
     Ok(())
 }
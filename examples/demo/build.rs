@@ -3,10 +3,25 @@ use fluorite_codegen::code_gen::rust::RustOptions;
 fn main() {
     // use with explicit options:
     let out_dir = std::env::var("OUT_DIR").unwrap();
-    let options = RustOptions::new(out_dir).with_any_type("serde_json::Value");
-    fluorite_codegen::compile_with_options(options, &["fluorite/demo.yaml"]).unwrap();
+    let options = RustOptions::new(out_dir.clone()).with_any_type("serde_json::Value");
+    let report = fluorite_codegen::compile_with_options(options, &["fluorite/demo.yaml"]).unwrap();
+    // fields boxed to break recursive types only show up in the build output as warnings:
+    for note in report.notes() {
+        println!("cargo:warning={}", note);
+    }
 
-    // or to use default options:
-    // let out_dir = std::env::var("OUT_DIR").unwrap();
-    // fluorite_codegen::compile(&["fluorite/demo.yaml"], out_dir.as_str()).unwrap();
+    // or to use default options, which keep `Any` values in `fluorite::Any`:
+    fluorite_codegen::compile(
+        &[
+            "fluorite/notices.yaml",
+            "fluorite/nested.yaml",
+            "fluorite/wire_names.yaml",
+        ],
+        out_dir.as_str(),
+    )
+    .unwrap();
+
+    // or with other derives, of which `Eq`, `Hash` and the like only build on `PartialEq`:
+    let options = RustOptions::new(out_dir).with_derives(&["Debug", "Clone"]);
+    fluorite_codegen::compile_with_options(options, &["fluorite/derives.yaml"]).unwrap();
 }
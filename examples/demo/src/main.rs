@@ -1,9 +1,30 @@
+#[allow(clippy::too_many_arguments, dead_code)]
 mod demo {
     include!(concat!(env!("OUT_DIR"), "/demo/mod.rs"));
 }
+use std::collections::HashMap;
+
 use demo::{Gender, User};
 
-use crate::demo::{AnObject, TestObjectEnum};
+use crate::demo::{
+    Account, Activity, ActivityKind, AnObject, Category, Celsius, Channel, Command, ConfigNode,
+    Directory, Endpoint, Label, LocalView, Location, Member, Notice, Plan, PostCode, Priority,
+    Profile, Published, Reading, Settings, Shape, Subscription, Task, Team, TeamId, TestObjectEnum,
+    TestObjectEnumKind, Theme, Token, TokenScope, UserId,
+};
+mod millis {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(value: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(value.as_millis() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_millis)
+    }
+}
+
 fn main() {
     let first_name = "f".to_string();
     let last_name = "l".to_string();
@@ -12,7 +33,6 @@ fn main() {
     let active = true;
     let info = None;
 
-    // let user = User::new(first_name, last_name, age, gender, active, info)
     let user = User {
         first_name,
         last_name,
@@ -22,7 +42,254 @@ fn main() {
         info,
     };
     println!("user: {:?}", user);
+    let built = User::builder()
+        .first_name("f")
+        .last_name("l")
+        .age(10u32)
+        .gender(Gender::Male)
+        .active(true)
+        .build()
+        .unwrap();
+    assert_eq!(built, user);
+    let error = User::builder().first_name("f").build().unwrap_err();
+    assert_eq!(error.to_string(), "missing field last_name of User");
+    let settings = Settings::builder().retries(5u32).build().unwrap();
+    assert_eq!(settings.retries, 5);
+    assert_eq!(settings.theme, "light");
+    assert!(!serde_json::to_string(&user).unwrap().contains("info"));
 
-    let o = TestObjectEnum::AnObject(AnObject::new("test".to_owned()));
+    let mut o = TestObjectEnum::from(AnObject {
+        field_a: "test".to_owned(),
+    });
+    assert!(o.is_an_object() && !o.is_plain_string());
+    assert_eq!(o.kind(), TestObjectEnumKind::AnObject);
+    if let Some(an_object) = o.as_an_object_mut() {
+        an_object.field_a.push('!');
+    }
+    assert_eq!(o.as_an_object().unwrap().field_a, "test!");
     println!("object enum: {:?}", o);
+    assert_eq!(o.into_an_object().unwrap().field_a, "test!");
+
+    let settings: Settings = serde_json::from_str("{}").unwrap();
+    assert_eq!(settings, Settings::default());
+    println!("settings: {:?}", settings);
+
+    let directory: Directory =
+        serde_json::from_str(r#"{"users": {"next_cursor": "c1"}, "settings": {"default": {}}}"#)
+            .unwrap();
+    println!("directory: {:?}", directory);
+
+    let token: Token = serde_json::from_str(r#"{"type": "bearer", "2fa-code": "123456"}"#).unwrap();
+    assert_eq!(token.r#type, "bearer");
+    assert_eq!(token._2fa_code.as_deref(), Some("123456"));
+    assert!(!token.self_);
+    assert_eq!(token.scope, TokenScope::ReadOnly);
+    println!("token: {}", serde_json::to_string(&token).unwrap());
+
+    let json = r#"{"displayName":"f","httpEndpoint":"https://example.com","plan":"HTTP_ONLY"}"#;
+    let profile: Profile = serde_json::from_str(json).unwrap();
+    assert_eq!(profile.http_endpoint, "https://example.com");
+    assert_eq!(profile.plan, Plan::HttpOnly);
+    assert_eq!(serde_json::to_string(&profile).unwrap(), json);
+
+    let account: Account =
+        serde_json::from_str(r#"{"id": "u1", "friends": ["u2"], "scores": {"u2": 1.5}}"#).unwrap();
+    assert_eq!(account.id, UserId::from("u1".to_owned()));
+    assert_eq!(account.id.len(), 2);
+    assert_eq!(account.email, "nobody@example.com");
+    assert!(account.friends.contains(&UserId::from("u2".to_owned())));
+    assert_eq!(*account.best_score, 0.0);
+    println!("account: {}", serde_json::to_string(&account).unwrap());
+
+    let team: Team = serde_json::from_str(r#"{"slug": "core", "parent": "eng"}"#).unwrap();
+    assert_eq!(team.slug, TeamId::from("core".to_owned()));
+    assert_eq!(team.parent, Some(TeamId::from("eng".to_owned())));
+    let member: Member =
+        serde_json::from_str(r#"{"team": "core", "roles": {"eng": "lead"}}"#).unwrap();
+    assert_eq!(member.team, team.slug);
+    assert!(member.past_teams.is_empty());
+    assert_eq!(member.roles[&TeamId::from("eng".to_owned())], "lead");
+    println!("member: {}", serde_json::to_string(&member).unwrap());
+
+    let task: Task = serde_json::from_str(r#"{"title": "t"}"#).unwrap();
+    assert_eq!(task.priority, Priority::Medium);
+    assert_eq!(i32::from(Priority::Medium), 2);
+    assert_eq!(Priority::try_from(10), Ok(Priority::Urgent));
+    assert!(Priority::try_from(3).is_err());
+    assert!(serde_json::from_str::<Task>(r#"{"title": "t", "priority": 3}"#).is_err());
+    let json = r#"{"title":"t","priority":10}"#;
+    let task: Task = serde_json::from_str(json).unwrap();
+    assert_eq!(serde_json::to_string(&task).unwrap(), json);
+    println!("task: {:?}", task);
+
+    assert_eq!(Gender::ALL, &[Gender::Male, Gender::Female]);
+    assert_eq!(Gender::Female.to_string(), "Female");
+    assert_eq!("Male".parse::<Gender>(), Ok(Gender::Male));
+    let error = "Other".parse::<Gender>().unwrap_err();
+    assert_eq!(error.to_string(), "invalid Gender value: Other");
+    let by_gender: HashMap<Gender, u32> = Gender::ALL.iter().map(|g| (*g, 0)).collect();
+    assert_eq!(by_gender.len(), 2);
+    assert!(Priority::Low < Priority::Urgent);
+    assert_eq!(Priority::Urgent.as_str(), "Urgent");
+    assert_eq!("sms".parse::<Channel>(), Ok(Channel::Sms));
+    let channel: Channel = "fax".parse().unwrap();
+    assert_eq!(channel.as_str(), "fax");
+
+    let channels: Vec<Channel> = serde_json::from_str(r#"["sms", "push"]"#).unwrap();
+    assert_eq!(
+        channels,
+        [Channel::Sms, Channel::Unknown("push".to_owned())]
+    );
+    assert_eq!(
+        serde_json::to_string(&channels).unwrap(),
+        r#"["sms","push"]"#
+    );
+    let json = r#"[{"kind":"Published","channel":"email"},{"kind":"Archived","at":1}]"#;
+    let notices: Vec<Notice> = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        notices[0],
+        Notice::Published {
+            channel: Channel::Email
+        }
+    );
+    assert!(matches!(&notices[1], Notice::Unknown(payload) if payload["at"] == 1));
+    let value: serde_json::Value = serde_json::from_str(json).unwrap();
+    assert_eq!(serde_json::to_value(&notices).unwrap(), value);
+    println!("notices: {:?}", notices);
+
+    let published = Published {
+        channel: Channel::Sms,
+    };
+    let notice = Notice::from(published.clone());
+    assert_eq!(Published::try_from(notice), Ok(published.clone()));
+    let activity = Activity::from(published.clone());
+    assert_eq!(Published::try_from(activity), Ok(published.clone()));
+    let retracted = Notice::Retracted {
+        reason: "typo".to_owned(),
+    };
+    assert_eq!(Published::try_from(retracted.clone()), Err(retracted));
+    let json = r#"{"name": "root", "children": [{"name": "leaf", "settings": {}}], "settings": {"k": "v"}}"#;
+    let root: ConfigNode = serde_json::from_str(json).unwrap();
+    let leaf = std::sync::Arc::clone(&root.children[0]);
+    assert_eq!(leaf.name, "leaf");
+    assert!(root.tags.is_empty());
+    let view: LocalView =
+        serde_json::from_str(&format!(r#"{{"nodes": {{"root": {}}}}}"#, json)).unwrap();
+    let shared = std::rc::Rc::clone(&view.nodes["root"]);
+    assert_eq!(shared.settings["k"].as_str(), "v");
+    assert_eq!(*view.description, "none");
+    let node = ConfigNode::builder()
+        .name("child")
+        .parent(root.clone())
+        .settings(std::collections::HashMap::new())
+        .build()
+        .unwrap();
+    assert_eq!(node.parent.as_ref().as_ref().unwrap().name, "root");
+    let theme = Theme::builder()
+        .accent(Label::from("red".to_owned()))
+        .build()
+        .unwrap();
+    assert_eq!(theme.accent.0, "red");
+
+    let json = r#"{"name": "rust", "parent": {"name": "languages"}}"#;
+    let category: Category = serde_json::from_str(json).unwrap();
+    assert_eq!(category.parent.as_ref().as_ref().unwrap().name, "languages");
+
+    let json = r#"{"address":"127.0.0.1","timeout":1500}"#;
+    let endpoint: Endpoint = serde_json::from_str(json).unwrap();
+    assert!(endpoint.address.is_loopback());
+    assert_eq!(endpoint.timeout, std::time::Duration::from_millis(1500));
+    assert_eq!(serde_json::to_string(&endpoint).unwrap(), json);
+
+    let subscription = Subscription {
+        last_published: published,
+    };
+    println!("subscription: {:?}", subscription);
+
+    let json = r#"{"shape":"Circle","data":{"radius":1.5}}"#;
+    let shape: Shape = serde_json::from_str(json).unwrap();
+    assert_eq!(shape, Shape::Circle { radius: 1.5 });
+    assert_eq!(serde_json::to_string(&shape).unwrap(), json);
+    assert_eq!(shape.as_circle(), Some(&1.5));
+    let mut shape = Shape::Rectangle {
+        width: 2.0,
+        height: 3.0,
+    };
+    if let Some((width, _)) = shape.as_rectangle_mut() {
+        *width = 4.0;
+    }
+    assert_eq!(shape.as_circle(), None);
+    assert_eq!(shape.into_rectangle(), Ok((4.0, 3.0)));
+
+    let commands: Vec<Command> =
+        serde_json::from_str(r#"[{"Start": {}}, {"Halt": {"reason": "done"}}]"#).unwrap();
+    assert_eq!(commands[0], Command::Start { delay: 0 });
+    let json = r#"[{"Start":{"delay":0}},{"Halt":{"reason":"done"}}]"#;
+    assert_eq!(serde_json::to_string(&commands).unwrap(), json);
+
+    let readings: Vec<Reading> = serde_json::from_str(r#"[21.5, "n/a", true]"#).unwrap();
+    assert_eq!(readings[0], Reading::Celsius(Celsius::from(21.5)));
+    assert_eq!(readings[1], Reading::Label(Label::from("n/a".to_owned())));
+    assert_eq!(readings[2], Reading::Unknown(serde_json::Value::Bool(true)));
+    assert_eq!(
+        serde_json::to_string(&readings).unwrap(),
+        r#"[21.5,"n/a",true]"#
+    );
+
+    let json = r#"[
+        {"type": "Published", "channel": "sms"},
+        {"type": "Plan", "content": "FREE"},
+        {"type": "ping"},
+        {"type": "count", "content": 3},
+        {"type": "tags", "content": ["a", "b"]},
+        {"type": "level", "content": 10},
+        {"type": "shape", "shape": "Square", "data": {"side": 2.0}},
+        {"type": "reading", "content": 21.5},
+        {"type": "scores", "content": {"a": 1}},
+        {"type": "maybe", "content": "core"}
+    ]"#;
+    let activities: Vec<Activity> = serde_json::from_str(json).unwrap();
+    assert_eq!(activities[3].as_count(), Some(&3));
+    assert_eq!(activities[6].kind(), ActivityKind::Shape);
+    assert!(activities[2].is_ping() && activities[2].as_count().is_none());
+    assert_eq!(
+        activities[0].clone().into_published(),
+        Ok(Published {
+            channel: Channel::Sms
+        })
+    );
+    assert_eq!(
+        activities[1],
+        Activity::Plan {
+            content: Plan::Free
+        }
+    );
+    assert_eq!(activities[2], Activity::Ping);
+    assert_eq!(activities[3], Activity::Count { content: 3 });
+    assert_eq!(
+        activities[5],
+        Activity::Level {
+            content: Priority::Urgent
+        }
+    );
+    assert_eq!(activities[6], Activity::Shape(Shape::Square { side: 2.0 }));
+    assert_eq!(
+        activities[9],
+        Activity::Maybe {
+            content: Some(TeamId::from("core".to_owned()))
+        }
+    );
+    let value: serde_json::Value = serde_json::from_str(json).unwrap();
+    assert_eq!(serde_json::to_value(&activities).unwrap(), value);
+    println!("activities: {:?}", activities);
+
+    let json = r#"[{"kind":"addr.v2","street":"Main St"},{"kind":"post_code","content":"10115"}]"#;
+    let locations: Vec<Location> = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        locations[1],
+        Location::PostCode {
+            content: PostCode::from("10115".to_owned())
+        }
+    );
+    assert_eq!(serde_json::to_string(&locations).unwrap(), json);
 }
@@ -0,0 +1,40 @@
+//! Types generated with `RustOptions::with_derives(&["Debug", "Clone"])`, which only derive
+//! what builds on those.
+
+#[allow(dead_code)]
+mod derives {
+    include!(concat!(env!("OUT_DIR"), "/derives/mod.rs"));
+}
+
+use derives::{Change, Key, Labeled, Level, Priority, Tag};
+
+#[test]
+fn types_without_partial_eq_compile_and_round_trip() -> anyhow::Result<()> {
+    let tag = Tag {
+        name: "a".to_owned(),
+        keys: vec![Key("k".to_owned())],
+        level: Level::High,
+    };
+    let json = serde_json::to_string(&tag)?;
+    assert_eq!(json, r#"{"name":"a","keys":["k"],"level":"High"}"#);
+    assert_eq!(
+        serde_json::to_string(&serde_json::from_str::<Tag>(&json)?)?,
+        json
+    );
+
+    let labeled = Labeled {
+        labels: ["x".to_owned()].into_iter().collect(),
+        priorities: [Priority::Urgent].into_iter().collect(),
+    };
+    assert_eq!(labeled.clone(), labeled);
+    assert!(Priority::Normal < Priority::Urgent);
+    assert_eq!("High".parse::<Level>()?.as_str(), "High");
+
+    let change: Change = serde_json::from_str(r#"{"type":"Renamed","key":"k"}"#)?;
+    assert!(change.is_renamed());
+    assert_eq!(
+        serde_json::to_string(&Change::Tagged(tag))?,
+        r#"{"type":"Tagged","name":"a","keys":["k"],"level":"High"}"#
+    );
+    Ok(())
+}
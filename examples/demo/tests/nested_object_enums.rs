@@ -0,0 +1,26 @@
+#[allow(dead_code)]
+mod nested {
+    include!(concat!(env!("OUT_DIR"), "/nested/mod.rs"));
+}
+
+use nested::{Inner, Other, Outer};
+
+#[test]
+fn object_enums_with_the_same_tag_are_nested() -> anyhow::Result<()> {
+    let outer = Outer::Inner {
+        content: Inner::A { x: 1 },
+    };
+    let json = serde_json::to_string(&outer)?;
+    assert_eq!(json, r#"{"type":"Inner","content":{"type":"A","x":1}}"#);
+    assert_eq!(serde_json::from_str::<Outer>(&json)?, outer);
+    Ok(())
+}
+
+#[test]
+fn object_enums_with_other_tags_are_flattened() -> anyhow::Result<()> {
+    let outer = Outer::Other(Other::A { x: 1 });
+    let json = serde_json::to_string(&outer)?;
+    assert_eq!(json, r#"{"type":"Other","kind":"A","x":1}"#);
+    assert_eq!(serde_json::from_str::<Outer>(&json)?, outer);
+    Ok(())
+}
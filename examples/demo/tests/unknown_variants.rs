@@ -0,0 +1,34 @@
+#[allow(dead_code)]
+mod notices {
+    include!(concat!(env!("OUT_DIR"), "/notices/mod.rs"));
+}
+
+use fluorite::Any;
+use notices::Notice;
+
+#[test]
+fn unknown_variants_keep_payloads_with_nulls() -> anyhow::Result<()> {
+    let json = r#"[{"kind":"Published","channel":"email"},{"kind":"Archived","reason":null,"tags":["a",null]}]"#;
+    let notices: Vec<Notice> = serde_json::from_str(json)?;
+    assert_eq!(
+        notices[0],
+        Notice::Published {
+            channel: "email".to_owned()
+        }
+    );
+    let Notice::Unknown(Any::Map(payload)) = &notices[1] else {
+        panic!(
+            "Archived is not kept as an unknown notice: {:?}",
+            notices[1]
+        );
+    };
+    assert_eq!(payload["reason"], Any::Null);
+    assert_eq!(
+        payload["tags"],
+        Any::List(vec![Any::String("a".to_owned()), Any::Null])
+    );
+
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    assert_eq!(serde_json::to_value(&notices)?, value);
+    Ok(())
+}
@@ -0,0 +1,32 @@
+#[allow(dead_code)]
+mod wire_names {
+    include!(concat!(env!("OUT_DIR"), "/wire_names/mod.rs"));
+}
+
+use wire_names::{Event, Mark, Quote};
+
+#[test]
+fn wire_names_with_quotes_and_backslashes_round_trip() -> anyhow::Result<()> {
+    let quote = Quote {
+        say_hi: "hello".to_owned(),
+        back_slash: "/".to_owned(),
+    };
+    let json = serde_json::to_string(&quote)?;
+    assert_eq!(json, r#"{"say \"hi\"":"hello","back\\slash":"/"}"#);
+    assert_eq!(serde_json::from_str::<Quote>(&json)?, quote);
+
+    let event = Event::AB {
+        content: "x".to_owned(),
+    };
+    let json = serde_json::to_string(&event)?;
+    assert_eq!(json, r#"{"ty\"pe":"a\"b","content":"x"}"#);
+    assert_eq!(serde_json::from_str::<Event>(&json)?, event);
+
+    for mark in Mark::ALL.iter().copied() {
+        assert_eq!(mark.as_str().parse::<Mark>()?, mark);
+        assert_eq!(serde_json::to_value(mark)?, mark.as_str());
+    }
+    assert_eq!(Mark::SayHi.as_str(), r#"say "hi""#);
+    assert_eq!(Mark::BackSlash.as_str(), r"back\slash");
+    Ok(())
+}
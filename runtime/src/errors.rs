@@ -0,0 +1,91 @@
+use std::{error::Error, fmt};
+
+/// The error of parsing a string into a generated enum which has no such value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseEnumError {
+    enum_name: &'static str,
+    value: String,
+}
+
+impl ParseEnumError {
+    pub fn new(enum_name: &'static str, value: impl Into<String>) -> Self {
+        ParseEnumError {
+            enum_name,
+            value: value.into(),
+        }
+    }
+
+    /// Name of the enum the value was parsed into.
+    pub fn enum_name(&self) -> &'static str {
+        self.enum_name
+    }
+
+    /// The value which failed to parse.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl fmt::Display for ParseEnumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid {} value: {}", self.enum_name, self.value)
+    }
+}
+
+impl Error for ParseEnumError {}
+
+/// The error of building a generated object without setting one of its required fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildError {
+    type_name: &'static str,
+    field: &'static str,
+}
+
+impl BuildError {
+    pub fn new(type_name: &'static str, field: &'static str) -> Self {
+        BuildError { type_name, field }
+    }
+
+    /// Name of the object being built.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The required field which was not set, named as in the schema.
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "missing field {} of {}", self.field, self.type_name)
+    }
+}
+
+impl Error for BuildError {}
+
+#[cfg(test)]
+mod test {
+    use super::{BuildError, ParseEnumError};
+
+    #[test]
+    fn test_parse_enum_error() {
+        let err = ParseEnumError::new("Gender", "Other");
+        assert_eq!(err.enum_name(), "Gender");
+        assert_eq!(err.value(), "Other");
+        assert_eq!(err.to_string(), "invalid Gender value: Other");
+        let err: Box<dyn std::error::Error> = Box::new(err);
+        assert_eq!(err.to_string(), "invalid Gender value: Other");
+    }
+
+    #[test]
+    fn test_build_error() {
+        let err = BuildError::new("User", "first_name");
+        assert_eq!(err.type_name(), "User");
+        assert_eq!(err.field(), "first_name");
+        assert_eq!(err.to_string(), "missing field first_name of User");
+        let err: Box<dyn std::error::Error> = Box::new(err);
+        assert_eq!(err.to_string(), "missing field first_name of User");
+    }
+}
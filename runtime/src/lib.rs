@@ -1,3 +1,6 @@
+mod errors;
+pub use errors::{BuildError, ParseEnumError};
+
 use std::collections::HashMap;
 
 use serde::{
@@ -8,6 +11,7 @@ use serde::{
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Any {
+    Null,
     String(String),
     Bool(bool),
     UInt32(u32),
@@ -26,6 +30,7 @@ impl Serialize for Any {
         S: Serializer,
     {
         match self {
+            Any::Null => serializer.serialize_unit(),
             Any::String(s) => serializer.serialize_str(s),
             Any::Bool(b) => serializer.serialize_bool(*b),
             Any::UInt32(u) => serializer.serialize_u32(*u),
@@ -70,6 +75,27 @@ impl<'de> Visitor<'de> for AnyVisitor {
         write!(formatter, "Expecting a valid Any type.")
     }
 
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(Any::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        self.visit_unit()
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Any::deserialize(deserializer)
+    }
+
     fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
     where
         E: Error,
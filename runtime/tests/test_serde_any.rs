@@ -34,3 +34,22 @@ fn can_serialize_deserialize() -> anyhow::Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn can_serialize_deserialize_null() -> anyhow::Result<()> {
+    let mut map = HashMap::new();
+    map.insert("reason".to_owned(), Any::Null);
+    map.insert(
+        "tags".to_owned(),
+        Any::List(vec![Any::String("a".to_owned()), Any::Null]),
+    );
+    let values = vec![Any::Null, Any::Map(map)];
+
+    for v in values {
+        let serialized = serde_json::to_string(&v)?;
+        let deserialized: Any = serde_json::from_str(serialized.as_str())?;
+        assert_eq!(v, deserialized);
+    }
+    assert_eq!(serde_json::to_string(&Any::Null)?, "null");
+    Ok(())
+}